@@ -1,6 +1,10 @@
 fn main() {
     cc::Build::new()
         .include("src/swisseph")
+        // Keep one copy of the library state for the whole process. Every call is serialized
+        // by SWE_LOCK anyway, and thread-local state would leave files opened by worker
+        // threads out of reach of `swe_close`.
+        .define("TLSOFF", None)
         .file("src/swisseph/sweph.c")
         .file("src/swisseph/swephlib.c")
         .file("src/swisseph/swedate.c")
@@ -1,4 +1,4 @@
-use astro_core::{calculate_core_chart, set_ephe_path, BirthData};
+use astro_core::{calculate_core_chart, set_ephe_path, BirthData, Calendar};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Point to bundled ephemeris data; change to your ephe directory if needed.
@@ -13,6 +13,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         second: 0.0,
         lat: 40.7128,  // +N latitude
         lon: -74.0060, // +E longitude, -W for west
+        altitude_m: None,
+        calendar: Calendar::Gregorian,
     };
 
     let chart = calculate_core_chart(&birth)?;
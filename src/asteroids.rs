@@ -0,0 +1,232 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::ephemeris::find_in_ephe_path;
+use crate::{AstroError, BirthData, Body, BodyPosition, ChartOptions, Ephemeris};
+
+/// Name of the Swiss Ephemeris asteroid name list in the ephemeris directory.
+pub const ASTEROID_NAMES_FILE: &str = "seasnam.txt";
+
+/// Calculate a numbered asteroid, e.g. 433 for Eros.
+///
+/// Ceres, Pallas, Juno, and Vesta (1-4) come from the main `seas_*.se1` files; any other
+/// number needs its own file (`ast0/se00433.se1`, or the shorter `se00433s.se1`) in the
+/// ephemeris path, and fails with `MissingEphemerisFile` naming it otherwise.
+pub fn calculate_asteroid(
+    birth: &BirthData,
+    number: u32,
+    options: &ChartOptions,
+) -> Result<BodyPosition, AstroError> {
+    Ephemeris::global().asteroid(birth, number, options)
+}
+
+/// Like `calculate_asteroid`, looking the number up by name in `seasnam.txt`.
+pub fn calculate_asteroid_by_name(
+    birth: &BirthData,
+    name: &str,
+    options: &ChartOptions,
+) -> Result<BodyPosition, AstroError> {
+    Ephemeris::global().asteroid_by_name(birth, name, options)
+}
+
+/// The `seasnam.txt` name index from the configured ephemeris path, read once.
+pub fn asteroid_names() -> Result<Arc<AsteroidNames>, AstroError> {
+    Ephemeris::global().asteroid_names()
+}
+
+/// Explain which file is missing when an asteroid's position fails.
+pub(crate) fn asteroid_body(
+    number: u32,
+    calculate: impl FnOnce(Body) -> Result<BodyPosition, AstroError>,
+) -> Result<BodyPosition, AstroError> {
+    calculate(Body::Asteroid(number)).map_err(|err| match err {
+        AstroError::MissingEphemerisFile { file, message } => AstroError::MissingEphemerisFile {
+            message: format!(
+                "asteroid {} needs {}, which is not part of the standard data files; {}",
+                number, file, message
+            ),
+            file,
+        },
+        other => other,
+    })
+}
+
+/// Path of `seasnam.txt` in a Swiss Ephemeris path list.
+pub(crate) fn names_file(ephe_path: &str) -> Result<std::path::PathBuf, AstroError> {
+    find_in_ephe_path(ephe_path, ASTEROID_NAMES_FILE).ok_or_else(|| {
+        AstroError::MissingEphemerisFile {
+            file: ASTEROID_NAMES_FILE.to_string(),
+            message: format!("{} not found in PATH '{}'", ASTEROID_NAMES_FILE, ephe_path),
+        }
+    })
+}
+
+/// Minor planet names from `seasnam.txt`, indexed by number and by name.
+#[derive(Debug, Clone)]
+pub struct AsteroidNames {
+    by_number: Vec<(u32, String)>, // sorted by number
+    by_name: Vec<(String, u32)>,   // sorted by lowercase name
+}
+
+impl AsteroidNames {
+    /// Read and parse a name list file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AstroError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| read_error(path, err))?;
+        Ok(AsteroidNames::parse(&text))
+    }
+
+    /// Parse lines of a zero-padded number followed by a name, e.g. `000433  Eros`.
+    /// Lines without a name are skipped.
+    pub fn parse(text: &str) -> Self {
+        let mut by_number: Vec<(u32, String)> = text
+            .lines()
+            .filter_map(parse_line)
+            .map(|(number, name)| (number, name.to_string()))
+            .collect();
+        by_number.sort_by_key(|&(number, _)| number);
+        let mut by_name: Vec<(String, u32)> = by_number
+            .iter()
+            .map(|(number, name)| (name.to_lowercase(), *number))
+            .collect();
+        // Stable, so a name used twice resolves to the lower number.
+        by_name.sort_by(|a, b| a.0.cmp(&b.0));
+        AsteroidNames { by_number, by_name }
+    }
+
+    /// Name of a numbered asteroid.
+    pub fn name(&self, number: u32) -> Option<&str> {
+        let index = self
+            .by_number
+            .binary_search_by_key(&number, |&(n, _)| n)
+            .ok()?;
+        Some(&self.by_number[index].1)
+    }
+
+    /// Number of the asteroid with this name, ignoring case.
+    pub fn number(&self, name: &str) -> Option<u32> {
+        let name = name.trim().to_lowercase();
+        let index = self.by_name.partition_point(|(n, _)| *n < name);
+        self.by_name
+            .get(index)
+            .filter(|(n, _)| *n == name)
+            .map(|&(_, number)| number)
+    }
+
+    /// `(number, name)` pairs in number order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.by_number
+            .iter()
+            .map(|(number, name)| (*number, name.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_number.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_number.is_empty()
+    }
+}
+
+fn parse_line(line: &str) -> Option<(u32, &str)> {
+    let line = line.trim();
+    let split = line.find(char::is_whitespace)?;
+    let number = line[..split].parse().ok()?;
+    let name = line[split..].trim();
+    (!name.is_empty()).then_some((number, name))
+}
+
+fn read_error(path: &Path, err: std::io::Error) -> AstroError {
+    AstroError::MissingEphemerisFile {
+        file: ASTEROID_NAMES_FILE.to_string(),
+        message: format!("cannot read {}: {}", path.display(), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Calendar;
+
+    #[test]
+    fn calculates_numbered_asteroids() {
+        let names =
+            AsteroidNames::parse("000022 \n000001  Ceres\n000433  Eros\n875150  2025 KX5\n");
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.name(433), Some("Eros"));
+        assert_eq!(names.number("eros"), Some(433));
+        assert_eq!(names.number("2025 KX5"), Some(875150));
+        assert_eq!(names.number("Vulcan"), None);
+        assert_eq!(names.name(22), None);
+
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping calculates_numbered_asteroids: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let ephemeris = Ephemeris::new(ephe_path).unwrap();
+        let birth = BirthData {
+            year: 2000,
+            month: 1,
+            day: 1,
+            hour: 12,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        let options = ChartOptions::default();
+
+        // Vesta (4) comes from the main asteroid files, like SE_VESTA.
+        let vesta = ephemeris.asteroid(&birth, 4, &options).unwrap();
+        assert_eq!(vesta.body, Body::Asteroid(4));
+        let jd = ephemeris.julian_day(&birth).unwrap();
+        let se_vesta = {
+            let _session = ephemeris.session().unwrap();
+            crate::calc_ut(jd.ut(), crate::ffi::SE_VESTA, crate::ffi::SEFLG_SWIEPH).unwrap()
+        };
+        assert!((se_vesta.xx[0] - vesta.longitude).abs() < 1e-9);
+        let by_name = ephemeris
+            .asteroid_by_name(&birth, "vesta", &options)
+            .unwrap();
+        assert_eq!(by_name.longitude, vesta.longitude);
+        // The index is read once per context and shared by its clones.
+        let names = ephemeris.asteroid_names().unwrap();
+        assert_eq!(names.number("Eros"), Some(433));
+        assert!(Arc::ptr_eq(
+            &names,
+            &ephemeris.clone().asteroid_names().unwrap()
+        ));
+
+        // Eros needs ast0/se00433.se1, which is not bundled.
+        match ephemeris.asteroid_by_name(&birth, "Eros", &options) {
+            Err(AstroError::MissingEphemerisFile { file, message }) => {
+                assert!(file.contains("se00433"), "{}", file);
+                assert!(message.starts_with("asteroid 433 needs"), "{}", message);
+            }
+            other => panic!("expected MissingEphemerisFile, got {other:?}"),
+        }
+        assert!(matches!(
+            ephemeris.asteroid_by_name(&birth, "Vulcan", &options),
+            Err(AstroError::UnknownBody(_))
+        ));
+        assert!(matches!(
+            ephemeris.asteroid(&birth, 0, &options),
+            Err(AstroError::InvalidInput(_))
+        ));
+        // Out-of-range numbers fail on every path, not only `asteroid`.
+        for number in [0, u32::MAX, i32::MAX as u32 - 9_999] {
+            assert!(matches!(
+                ephemeris.body(&birth, Body::Asteroid(number), &options),
+                Err(AstroError::InvalidInput(_))
+            ));
+        }
+    }
+}
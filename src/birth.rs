@@ -0,0 +1,227 @@
+use crate::{AstroError, BirthData, Calendar, LeapSeconds};
+
+impl BirthData {
+    /// Start building birth data that is validated on `build`.
+    pub fn builder() -> BirthDataBuilder {
+        BirthDataBuilder::default()
+    }
+
+    /// Check every field before it is passed to the C library. Date and time fields fail
+    /// with `InvalidDate`, location fields with `InvalidCoordinates`.
+    ///
+    /// Seconds may reach 60.999 only at 23:59 UTC on a day that ended with a leap second.
+    pub fn validate(&self) -> Result<(), AstroError> {
+        self.validate_with(&LeapSeconds::builtin())
+    }
+
+    /// Like `validate`, accepting leap seconds from `leap_seconds`.
+    pub fn validate_with(&self, leap_seconds: &LeapSeconds) -> Result<(), AstroError> {
+        if !(1..=12).contains(&self.month) {
+            return Err(invalid_date(format!(
+                "month must be 1-12, got {}",
+                self.month
+            )));
+        }
+        // Checks the day against the month in the birth's calendar, including adoption gaps.
+        self.calendar
+            .julian_day(self.year, self.month, self.day, 0.0)?;
+        if !(0..=23).contains(&self.hour) {
+            return Err(invalid_date(format!(
+                "hour must be 0-23, got {}",
+                self.hour
+            )));
+        }
+        if !(0..=59).contains(&self.minute) {
+            return Err(invalid_date(format!(
+                "minute must be 0-59, got {}",
+                self.minute
+            )));
+        }
+        if !(0.0..61.0).contains(&self.second) {
+            return Err(invalid_date(format!(
+                "second must be 0-59.999 (60.999 during a leap second), got {}",
+                self.second
+            )));
+        }
+        if self.second >= 60.0 && !self.is_leap_second(leap_seconds) {
+            return Err(invalid_date(format!(
+                "{}-{:02}-{:02} {:02}:{:02}:{} is not a leap second",
+                self.year, self.month, self.day, self.hour, self.minute, self.second
+            )));
+        }
+        if !(-90.0..=90.0).contains(&self.lat) {
+            return Err(invalid_coordinates(format!(
+                "latitude must be -90 to 90 degrees, got {}",
+                self.lat
+            )));
+        }
+        if !(-180.0..=180.0).contains(&self.lon) {
+            return Err(invalid_coordinates(format!(
+                "longitude must be -180 to 180 degrees, got {}",
+                self.lon
+            )));
+        }
+        if let Some(altitude) = self.altitude_m {
+            if !altitude.is_finite() {
+                return Err(invalid_coordinates(format!(
+                    "altitude must be finite, got {}",
+                    altitude
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_leap_second(&self, leap_seconds: &LeapSeconds) -> bool {
+        let gregorian = self.calendar.gregflag(self.year, self.month, self.day);
+        gregorian.is_ok_and(|flag| flag == crate::ffi::SE_GREG_CAL)
+            && self.hour == 23
+            && self.minute == 59
+            && leap_seconds.is_leap_second_day(self.year, self.month, self.day)
+    }
+}
+
+/// Builder for `BirthData`; `build` runs `BirthData::validate`.
+#[derive(Debug, Clone, Default)]
+pub struct BirthDataBuilder {
+    date: Option<(i32, i32, i32)>,
+    time: (i32, i32, f64),
+    location: Option<(f64, f64)>,
+    altitude_m: Option<f64>,
+    calendar: Calendar,
+}
+
+impl BirthDataBuilder {
+    /// Calendar date, Gregorian unless set with `calendar`. Required.
+    pub fn date(mut self, year: i32, month: i32, day: i32) -> Self {
+        self.date = Some((year, month, day));
+        self
+    }
+
+    /// UTC time of day. Defaults to midnight.
+    pub fn time(mut self, hour: i32, minute: i32, second: f64) -> Self {
+        self.time = (hour, minute, second);
+        self
+    }
+
+    /// Latitude (+N) and longitude (+E) in degrees. Required.
+    pub fn location(mut self, lat: f64, lon: f64) -> Self {
+        self.location = Some((lat, lon));
+        self
+    }
+
+    /// Elevation above sea level in meters, used by topocentric charts.
+    pub fn altitude(mut self, meters: f64) -> Self {
+        self.altitude_m = Some(meters);
+        self
+    }
+
+    /// Calendar the date is written in. Defaults to Gregorian.
+    pub fn calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn build(self) -> Result<BirthData, AstroError> {
+        let (year, month, day) = self
+            .date
+            .ok_or_else(|| invalid_date("birth date is required".to_string()))?;
+        let (lat, lon) = self
+            .location
+            .ok_or_else(|| invalid_coordinates("birth location is required".to_string()))?;
+        let (hour, minute, second) = self.time;
+        let birth = BirthData {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            lat,
+            lon,
+            altitude_m: self.altitude_m,
+            calendar: self.calendar,
+        };
+        birth.validate()?;
+        Ok(birth)
+    }
+}
+
+fn invalid_date(reason: String) -> AstroError {
+    AstroError::InvalidDate(reason)
+}
+
+fn invalid_coordinates(reason: String) -> AstroError {
+    AstroError::InvalidCoordinates(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_reason(result: Result<BirthData, AstroError>) -> String {
+        match result {
+            Err(AstroError::InvalidDate(reason)) => reason,
+            other => panic!("expected InvalidDate, got {other:?}"),
+        }
+    }
+
+    fn coordinates_reason(result: Result<BirthData, AstroError>) -> String {
+        match result {
+            Err(AstroError::InvalidCoordinates(reason)) => reason,
+            other => panic!("expected InvalidCoordinates, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validates_birth_data_ranges() {
+        let base = || BirthData::builder().date(1990, 1, 1).location(0.0, 0.0);
+        let birth = base().time(10, 30, 15.5).altitude(120.0).build().unwrap();
+        assert_eq!((birth.hour, birth.minute, birth.second), (10, 30, 15.5));
+        assert_eq!(birth.altitude_m, Some(120.0));
+
+        assert!(date_reason(base().date(1990, 13, 1).build()).contains("month"));
+        assert!(
+            date_reason(base().date(1990, 2, 29).build()).contains("not a valid Gregorian date")
+        );
+        assert!(base().date(2000, 2, 29).build().is_ok());
+        assert!(base()
+            .date(1500, 2, 29)
+            .calendar(Calendar::Julian)
+            .build()
+            .is_ok());
+        assert!(date_reason(base().time(10, 75, 0.0).build()).contains("minute"));
+        assert!(date_reason(base().time(24, 0, 0.0).build()).contains("hour"));
+        assert!(coordinates_reason(base().location(200.0, 0.0).build()).contains("latitude"));
+        assert!(coordinates_reason(base().location(0.0, f64::NAN).build()).contains("longitude"));
+        assert!(coordinates_reason(base().altitude(f64::INFINITY).build()).contains("altitude"));
+        assert!(
+            coordinates_reason(BirthData::builder().date(1990, 1, 1).build()).contains("location")
+        );
+
+        // 2016-12-31 ended with a leap second; 2017-12-31 did not.
+        let leap = base().date(2016, 12, 31).time(23, 59, 60.5).build();
+        assert!(leap.is_ok());
+        let not_leap = base().date(2017, 12, 31).time(23, 59, 60.5).build();
+        assert!(date_reason(not_leap).contains("not a leap second"));
+        let wrong_minute = base().date(2016, 12, 31).time(12, 0, 60.0).build();
+        assert!(date_reason(wrong_minute).contains("not a leap second"));
+        assert!(date_reason(base().time(0, 0, 61.0).build()).contains("second"));
+
+        // Far-off years fail cleanly instead of overflowing the leap-second lookup.
+        let far = BirthData {
+            year: 300_000,
+            ..base()
+                .date(2016, 12, 31)
+                .time(23, 59, 60.0)
+                .build()
+                .unwrap()
+        };
+        assert!(matches!(far.validate(), Err(AstroError::InvalidDate(_))));
+        let far = BirthData {
+            year: -300_000,
+            ..far
+        };
+        assert!(matches!(far.validate(), Err(AstroError::InvalidDate(_))));
+    }
+}
@@ -0,0 +1,159 @@
+use libc::{c_char, c_int};
+
+use crate::{ffi, AstroError};
+
+/// Calendar in which a birth date is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Calendar {
+    /// Proleptic Gregorian calendar, also before 1582.
+    #[default]
+    Gregorian,
+    /// Proleptic Julian calendar.
+    Julian,
+    /// Julian before a country's switch to the Gregorian calendar, Gregorian from then on.
+    /// Dates dropped by the switch (e.g. 1752-09-03 to 1752-09-13 in Britain) are rejected.
+    Adoption(GregorianAdoption),
+}
+
+/// First Gregorian date in use after a country adopted the calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GregorianAdoption {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+}
+
+impl GregorianAdoption {
+    /// Papal States, Spain, Portugal, and Poland: Thursday 1582-10-04 (Julian) was followed
+    /// by Friday 1582-10-15.
+    pub const ROME: GregorianAdoption = GregorianAdoption::new(1582, 10, 15);
+    /// Great Britain and its colonies.
+    pub const BRITAIN: GregorianAdoption = GregorianAdoption::new(1752, 9, 14);
+    pub const SWEDEN: GregorianAdoption = GregorianAdoption::new(1753, 3, 1);
+    pub const RUSSIA: GregorianAdoption = GregorianAdoption::new(1918, 2, 14);
+    pub const GREECE: GregorianAdoption = GregorianAdoption::new(1923, 3, 1);
+
+    pub const fn new(year: i32, month: i32, day: i32) -> Self {
+        GregorianAdoption { year, month, day }
+    }
+
+    fn julian_day(self) -> f64 {
+        unsafe { ffi::swe_julday(self.year, self.month, self.day, 0.0, ffi::SE_GREG_CAL) }
+    }
+}
+
+impl Calendar {
+    /// Julian day number for a date and decimal hour, checking that the date exists
+    /// in this calendar. Years are astronomical: 0 is 1 BCE, -1 is 2 BCE.
+    pub fn julian_day(self, year: i32, month: i32, day: i32, hour: f64) -> Result<f64, AstroError> {
+        let gregflag = self.gregflag(year, month, day)?;
+        let code = if gregflag == ffi::SE_GREG_CAL {
+            'g'
+        } else {
+            'j'
+        };
+        let mut tjd = 0.0;
+        let rc =
+            unsafe { ffi::swe_date_conversion(year, month, day, hour, code as c_char, &mut tjd) };
+        if rc < 0 {
+            return Err(AstroError::InvalidDate(format!(
+                "{}-{:02}-{:02} is not a valid {} date",
+                year,
+                month,
+                day,
+                if gregflag == ffi::SE_GREG_CAL {
+                    "Gregorian"
+                } else {
+                    "Julian"
+                }
+            )));
+        }
+        Ok(tjd)
+    }
+
+    /// Calendar date `(year, month, day, hour)` of a Julian day number.
+    pub fn date(self, tjd: f64) -> (i32, i32, i32, f64) {
+        let gregflag = self.gregflag_at(tjd);
+        let (mut year, mut month, mut day, mut hour) = (0, 0, 0, 0.0);
+        unsafe {
+            ffi::swe_revjul(tjd, gregflag, &mut year, &mut month, &mut day, &mut hour);
+        }
+        (year, month, day, hour)
+    }
+
+    /// `SE_GREG_CAL` or `SE_JUL_CAL` for expressing a Julian day number in this calendar.
+    pub(crate) fn gregflag_at(self, tjd: f64) -> c_int {
+        match self {
+            Calendar::Gregorian => ffi::SE_GREG_CAL,
+            Calendar::Julian => ffi::SE_JUL_CAL,
+            Calendar::Adoption(adoption) if tjd < adoption.julian_day() => ffi::SE_JUL_CAL,
+            Calendar::Adoption(_) => ffi::SE_GREG_CAL,
+        }
+    }
+
+    /// `SE_GREG_CAL` or `SE_JUL_CAL` for a date written in this calendar.
+    pub(crate) fn gregflag(self, year: i32, month: i32, day: i32) -> Result<c_int, AstroError> {
+        let adoption = match self {
+            Calendar::Gregorian => return Ok(ffi::SE_GREG_CAL),
+            Calendar::Julian => return Ok(ffi::SE_JUL_CAL),
+            Calendar::Adoption(adoption) => adoption,
+        };
+        // Adoption dates fall at midnight, so compare whole days (JD n.5 is 00:00).
+        let first_gregorian = adoption.julian_day();
+        let as_julian = unsafe { ffi::swe_julday(year, month, day, 0.0, ffi::SE_JUL_CAL) };
+        if as_julian < first_gregorian {
+            return Ok(ffi::SE_JUL_CAL);
+        }
+        let as_gregorian = unsafe { ffi::swe_julday(year, month, day, 0.0, ffi::SE_GREG_CAL) };
+        if as_gregorian >= first_gregorian {
+            return Ok(ffi::SE_GREG_CAL);
+        }
+        Err(AstroError::InvalidDate(format!(
+            "{}-{:02}-{:02} was skipped when the Gregorian calendar was adopted on {}-{:02}-{:02}",
+            year, month, day, adoption.year, adoption.month, adoption.day
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_calendars() {
+        // The day after Julian 1582-10-04 is Gregorian 1582-10-15.
+        let last_julian = Calendar::Julian.julian_day(1582, 10, 4, 0.0).unwrap();
+        let first_gregorian = Calendar::Gregorian.julian_day(1582, 10, 15, 0.0).unwrap();
+        assert_eq!(first_gregorian - last_julian, 1.0);
+
+        // Newton was born on 1642-12-25 in England, still on the Julian calendar.
+        let britain = Calendar::Adoption(GregorianAdoption::BRITAIN);
+        let newton = britain.julian_day(1642, 12, 25, 0.0).unwrap();
+        assert_eq!(
+            newton,
+            Calendar::Julian.julian_day(1642, 12, 25, 0.0).unwrap()
+        );
+        assert_eq!(Calendar::Gregorian.date(newton), (1643, 1, 4, 0.0));
+        assert_eq!(britain.date(newton), (1642, 12, 25, 0.0));
+        // 200 Julian years later, Britain's dates are Gregorian.
+        assert_eq!(britain.date(newton + 73_050.0), (1843, 1, 6, 0.0));
+
+        // 1752-09-03 to 1752-09-13 never happened in Britain.
+        assert!(britain.julian_day(1752, 9, 2, 0.0).is_ok());
+        assert!(matches!(
+            britain.julian_day(1752, 9, 5, 0.0),
+            Err(AstroError::InvalidDate(_))
+        ));
+        assert_eq!(
+            britain.julian_day(1752, 9, 14, 0.0).unwrap()
+                - britain.julian_day(1752, 9, 2, 0.0).unwrap(),
+            1.0
+        );
+
+        // Astronomical year 0 is 1 BCE, a Julian leap year; Gregorian 1900 was not a leap year.
+        let bce = Calendar::Julian.julian_day(0, 2, 29, 12.0).unwrap();
+        assert_eq!(Calendar::Julian.date(bce), (0, 2, 29, 12.0));
+        assert!(Calendar::Julian.julian_day(1900, 2, 29, 0.0).is_ok());
+        assert!(Calendar::Gregorian.julian_day(1900, 2, 29, 0.0).is_err());
+    }
+}
@@ -0,0 +1,663 @@
+use libc::c_int;
+
+use crate::ephemeris::checked_source;
+use crate::ZodiacPosition;
+use crate::{calc_ut, ffi, JulianDay};
+use crate::{AstroError, BirthData, Ephemeris, EphemerisSource, PolarFallback, Sign, Zodiac};
+
+/// Bodies supported by the full chart calculation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Body {
+    Sun,
+    Moon,
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+    Chiron,
+    MeanNode,
+    TrueNode,
+    /// Numbered minor planet, e.g. `Asteroid(433)` for Eros. Besides Ceres to Vesta (1-4),
+    /// each needs its own `se?????.se1` file; see `calculate_asteroid`.
+    Asteroid(u32),
+}
+
+// Minor planet numbers above this would overflow `SE_AST_OFFSET + n`.
+const MAX_ASTEROID: u32 = (c_int::MAX - ffi::SE_AST_OFFSET) as u32;
+
+impl Body {
+    /// Every body computed by `calculate_full_chart`, in chart order.
+    pub const ALL: [Body; 13] = [
+        Body::Sun,
+        Body::Moon,
+        Body::Mercury,
+        Body::Venus,
+        Body::Mars,
+        Body::Jupiter,
+        Body::Saturn,
+        Body::Uranus,
+        Body::Neptune,
+        Body::Pluto,
+        Body::Chiron,
+        Body::MeanNode,
+        Body::TrueNode,
+    ];
+
+    /// Lowercase identifier, e.g. `"sun"` or `"mean_node"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Body::Sun => "sun",
+            Body::Moon => "moon",
+            Body::Mercury => "mercury",
+            Body::Venus => "venus",
+            Body::Mars => "mars",
+            Body::Jupiter => "jupiter",
+            Body::Saturn => "saturn",
+            Body::Uranus => "uranus",
+            Body::Neptune => "neptune",
+            Body::Pluto => "pluto",
+            Body::Chiron => "chiron",
+            Body::MeanNode => "mean_node",
+            Body::TrueNode => "true_node",
+            Body::Asteroid(_) => "asteroid",
+        }
+    }
+
+    /// Swiss Ephemeris planet number. Fails for asteroid numbers outside 1 to `MAX_ASTEROID`.
+    pub(crate) fn ipl(self) -> Result<c_int, AstroError> {
+        Ok(match self {
+            Body::Sun => ffi::SE_SUN,
+            Body::Moon => ffi::SE_MOON,
+            Body::Mercury => ffi::SE_MERCURY,
+            Body::Venus => ffi::SE_VENUS,
+            Body::Mars => ffi::SE_MARS,
+            Body::Jupiter => ffi::SE_JUPITER,
+            Body::Saturn => ffi::SE_SATURN,
+            Body::Uranus => ffi::SE_URANUS,
+            Body::Neptune => ffi::SE_NEPTUNE,
+            Body::Pluto => ffi::SE_PLUTO,
+            Body::Chiron => ffi::SE_CHIRON,
+            Body::MeanNode => ffi::SE_MEAN_NODE,
+            Body::TrueNode => ffi::SE_TRUE_NODE,
+            Body::Asteroid(number @ 1..=MAX_ASTEROID) => ffi::SE_AST_OFFSET + number as c_int,
+            Body::Asteroid(number) => {
+                return Err(AstroError::InvalidInput(format!(
+                    "asteroid number must be 1-{}, got {}",
+                    MAX_ASTEROID, number
+                )))
+            }
+        })
+    }
+
+    /// Average geocentric speed in degrees/day, used to scale the station threshold.
+    pub(crate) fn mean_daily_motion(self) -> f64 {
+        match self {
+            Body::Sun | Body::Mercury | Body::Venus => 0.9856,
+            Body::Moon => 13.176,
+            Body::Mars => 0.524,
+            Body::Jupiter => 0.0831,
+            Body::Saturn => 0.0335,
+            Body::Uranus => 0.0117,
+            Body::Neptune => 0.006,
+            Body::Pluto => 0.004,
+            Body::Chiron => 0.0193,
+            Body::MeanNode | Body::TrueNode => 0.053,
+            // Typical of the main belt; near-Earth objects move faster.
+            Body::Asteroid(_) => 0.25,
+        }
+    }
+}
+
+/// Direction of apparent motion along the ecliptic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MotionState {
+    Direct,
+    Retrograde,
+    /// Nearly stationary and slowing down, about to turn retrograde.
+    StationaryRetrograde,
+    /// Nearly stationary and speeding up, about to turn direct.
+    StationaryDirect,
+}
+
+impl MotionState {
+    /// Classify motion from longitude speed and its rate of change (degrees/day and degrees/day²).
+    ///
+    /// A body counts as stationary while `|speed|` is below `station_speed`.
+    pub fn classify(speed: f64, acceleration: f64, station_speed: f64) -> Self {
+        if speed.abs() < station_speed {
+            if acceleration < 0.0 {
+                MotionState::StationaryRetrograde
+            } else {
+                MotionState::StationaryDirect
+            }
+        } else if speed < 0.0 {
+            MotionState::Retrograde
+        } else {
+            MotionState::Direct
+        }
+    }
+
+    /// True for `Retrograde` and `StationaryDirect`, the states of a retrograde loop. Near a
+    /// station the body may still move the other way; use `BodyPosition::is_retrograde`
+    /// for the direction of motion.
+    pub fn is_retrograde(self) -> bool {
+        matches!(
+            self,
+            MotionState::Retrograde | MotionState::StationaryDirect
+        )
+    }
+
+    pub fn is_stationary(self) -> bool {
+        matches!(
+            self,
+            MotionState::StationaryRetrograde | MotionState::StationaryDirect
+        )
+    }
+}
+
+/// Point of view positions are computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CenterMode {
+    /// Apparent positions seen from the Earth's center.
+    #[default]
+    Geocentric,
+    /// Seen from the birth location, including `altitude_m`. Matters most for the Moon,
+    /// whose parallax reaches about 1°.
+    Topocentric,
+    /// Seen from the Sun's center.
+    Heliocentric,
+    /// Seen from the solar system barycenter.
+    Barycentric,
+}
+
+impl CenterMode {
+    /// Whether `body` has a meaningful position from this center. The Sun has no
+    /// heliocentric position, and the lunar nodes only exist relative to the Earth.
+    pub fn supports(self, body: Body) -> bool {
+        match self {
+            CenterMode::Geocentric | CenterMode::Topocentric => true,
+            CenterMode::Heliocentric => {
+                !matches!(body, Body::Sun | Body::MeanNode | Body::TrueNode)
+            }
+            CenterMode::Barycentric => !matches!(body, Body::MeanNode | Body::TrueNode),
+        }
+    }
+
+    fn flags(self) -> c_int {
+        match self {
+            CenterMode::Geocentric => 0,
+            CenterMode::Topocentric => ffi::SEFLG_TOPOCTR,
+            CenterMode::Heliocentric => ffi::SEFLG_HELCTR,
+            CenterMode::Barycentric => ffi::SEFLG_BARYCTR,
+        }
+    }
+}
+
+/// Options for `calculate_full_chart_with` and `calculate_houses_with`.
+#[derive(Debug, Clone)]
+pub struct ChartOptions {
+    /// Fraction of a body's mean daily motion below which it is reported as stationary.
+    pub station_threshold: f64,
+    /// Tropical (default) or sidereal longitudes, applied to bodies and house cusps.
+    pub zodiac: Zodiac,
+    /// What to do when a house system cannot be computed at the birth latitude.
+    pub polar_fallback: PolarFallback,
+    /// Geocentric (default), topocentric, heliocentric, or barycentric positions.
+    pub center: CenterMode,
+    /// Fail instead of silently falling back to the Moshier ephemeris when `.se1` files
+    /// are missing.
+    pub strict_ephemeris: bool,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        ChartOptions {
+            station_threshold: 0.05,
+            zodiac: Zodiac::Tropical,
+            polar_fallback: PolarFallback::Report,
+            center: CenterMode::Geocentric,
+            strict_ephemeris: false,
+        }
+    }
+}
+
+impl ChartOptions {
+    /// Push sidereal and observer settings into the C library and return the matching
+    /// calculation flags, shared by body and house calculations. Topocentric positions
+    /// need the observer from `birth`.
+    pub(crate) fn apply(&self, birth: Option<&BirthData>) -> Result<c_int, AstroError> {
+        let iflag = self.zodiac.apply()? | self.center.flags();
+        if self.center == CenterMode::Topocentric {
+            let birth = birth.ok_or_else(|| {
+                AstroError::InvalidInput(
+                    "topocentric positions need the observer's location".into(),
+                )
+            })?;
+            let altitude = birth.altitude_m.unwrap_or(0.0);
+            if !altitude.is_finite() {
+                return Err(AstroError::InvalidCoordinates(format!(
+                    "altitude must be finite, got {}",
+                    altitude
+                )));
+            }
+            unsafe {
+                ffi::swe_set_topo(birth.lon, birth.lat, altitude);
+            }
+        }
+        Ok(iflag)
+    }
+}
+
+/// Ecliptic and equatorial position of a single body.
+#[derive(Debug, Clone)]
+pub struct BodyPosition {
+    pub body: Body,
+    pub longitude: f64,         // ecliptic longitude in degrees, 0-360
+    pub latitude: f64,          // ecliptic latitude in degrees
+    pub distance: f64,          // distance in AU
+    pub longitude_speed: f64,   // degrees/day, negative when retrograde
+    pub latitude_speed: f64,    // degrees/day
+    pub distance_speed: f64,    // AU/day
+    pub right_ascension: f64,   // degrees, 0-360, equinox of date
+    pub declination: f64,       // degrees north (+) or south (-) of the celestial equator
+    pub declination_speed: f64, // degrees/day
+    pub motion: MotionState,
+    pub source: EphemerisSource, // ephemeris that produced this position
+    pub sign: Sign,
+    pub sign_degree: f64, // degrees within the sign, 0-30
+}
+
+impl BodyPosition {
+    fn from_calc(
+        body: Body,
+        xx: [f64; 6],
+        eq: [f64; 6],
+        motion: MotionState,
+        source: EphemerisSource,
+    ) -> Self {
+        let longitude = xx[0].rem_euclid(360.0);
+        BodyPosition {
+            body,
+            longitude,
+            latitude: xx[1],
+            distance: xx[2],
+            longitude_speed: xx[3],
+            latitude_speed: xx[4],
+            distance_speed: xx[5],
+            right_ascension: eq[0].rem_euclid(360.0),
+            declination: eq[1],
+            declination_speed: eq[4],
+            motion,
+            source,
+            sign: Sign::from_longitude(longitude),
+            sign_degree: longitude % 30.0,
+        }
+    }
+
+    /// True while the longitude decreases. Unlike `MotionState::is_retrograde`, this is
+    /// exact near a station, where the speed can have either sign.
+    pub fn is_retrograde(&self) -> bool {
+        self.longitude_speed < 0.0
+    }
+
+    /// Longitude as sign, degree, minute, and second; use `ZodiacPosition::rounded` to round.
+    pub fn zodiac_position(&self) -> ZodiacPosition {
+        ZodiacPosition::from_longitude(self.longitude)
+    }
+}
+
+/// Positions for every body in `Body::ALL` that the chart's `CenterMode` supports.
+#[derive(Debug, Clone)]
+pub struct FullChart {
+    pub positions: Vec<BodyPosition>,
+}
+
+impl FullChart {
+    /// Look up the position of a body in the chart.
+    pub fn get(&self, body: Body) -> Option<&BodyPosition> {
+        self.positions.iter().find(|p| p.body == body)
+    }
+}
+
+/// Calculate positions for the Sun, Moon, planets, Chiron, and the lunar nodes.
+pub fn calculate_full_chart(birth: &BirthData) -> Result<FullChart, AstroError> {
+    calculate_full_chart_with(birth, &ChartOptions::default())
+}
+
+/// Like `calculate_full_chart`, with explicit calculation options.
+pub fn calculate_full_chart_with(
+    birth: &BirthData,
+    options: &ChartOptions,
+) -> Result<FullChart, AstroError> {
+    Ephemeris::global().full_chart(birth, options)
+}
+
+pub(crate) fn full_chart_at(
+    ephe_flag: c_int,
+    jd: JulianDay,
+    observer: Option<&BirthData>,
+    options: &ChartOptions,
+) -> Result<FullChart, AstroError> {
+    let tjd_ut = jd.ut();
+    let iflag = ephe_flag | ffi::SEFLG_SPEED | options.apply(observer)?;
+
+    let positions = Body::ALL
+        .iter()
+        .filter(|&&body| options.center.supports(body))
+        .map(|&body| body_position(tjd_ut, body, iflag, options))
+        .collect::<Result<Vec<_>, AstroError>>()?;
+
+    Ok(FullChart { positions })
+}
+
+/// Calculate the position of a single body.
+///
+/// Fails with `InvalidInput` when the body has no position from `options.center`,
+/// e.g. the heliocentric Sun.
+pub fn calculate_body(
+    birth: &BirthData,
+    body: Body,
+    options: &ChartOptions,
+) -> Result<BodyPosition, AstroError> {
+    Ephemeris::global().body(birth, body, options)
+}
+
+pub(crate) fn single_body(
+    ephe_flag: c_int,
+    jd: JulianDay,
+    observer: Option<&BirthData>,
+    body: Body,
+    options: &ChartOptions,
+) -> Result<BodyPosition, AstroError> {
+    if !options.center.supports(body) {
+        return Err(AstroError::InvalidInput(format!(
+            "{} has no {:?} position",
+            body.name(),
+            options.center
+        )));
+    }
+    let iflag = ephe_flag | ffi::SEFLG_SPEED | options.apply(observer)?;
+    body_position(jd.ut(), body, iflag, options)
+}
+
+// Step used to estimate the change in speed around a station.
+const STATION_STEP_DAYS: f64 = 0.1;
+
+fn body_position(
+    tjd_ut: f64,
+    body: Body,
+    iflag: c_int,
+    options: &ChartOptions,
+) -> Result<BodyPosition, AstroError> {
+    let ipl = body.ipl()?;
+    let calc = calc_ut(tjd_ut, ipl, iflag)?;
+    let source = checked_source(iflag, &calc, options.strict_ephemeris)?;
+    let xx = calc.xx;
+
+    let station_speed = options.station_threshold * body.mean_daily_motion();
+    let acceleration = if xx[3].abs() < station_speed {
+        let later = calc_ut(tjd_ut + STATION_STEP_DAYS, ipl, iflag)?.xx;
+        (later[3] - xx[3]) / STATION_STEP_DAYS
+    } else {
+        0.0
+    };
+    let motion = MotionState::classify(xx[3], acceleration, station_speed);
+
+    // Right ascension and declination are always measured from the true equinox of date.
+    let eq_flag = (iflag & !ffi::SEFLG_SIDEREAL) | ffi::SEFLG_EQUATORIAL;
+    let eq = calc_ut(tjd_ut, ipl, eq_flag)?.xx;
+
+    Ok(BodyPosition::from_calc(body, xx, eq, motion, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{set_ephe_path, Calendar};
+    use std::path::Path;
+
+    #[test]
+    fn calculates_full_chart() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping calculates_full_chart: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 1990,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+
+        let chart = calculate_full_chart(&birth).expect("chart should compute");
+
+        assert_eq!(chart.positions.len(), Body::ALL.len());
+        // Sun at 1990-01-01 is around 10° Capricorn.
+        let sun = chart.get(Body::Sun).expect("sun position");
+        assert_eq!(sun.sign, Sign::Capricorn);
+        assert!((sun.sign_degree - 10.0).abs() < 1.0);
+        for pos in &chart.positions {
+            assert!((0.0..360.0).contains(&pos.longitude));
+            assert!((0.0..30.0).contains(&pos.sign_degree));
+        }
+        assert!(sun.longitude_speed > 0.9);
+        assert_eq!(sun.source, EphemerisSource::Swiss);
+        // Near the December solstice the Sun sits close to its southern limit.
+        assert!(sun.declination < -22.5 && sun.declination > -23.5);
+        assert!(sun.right_ascension > 270.0 && sun.right_ascension < 290.0);
+        assert_eq!(sun.motion, MotionState::Direct);
+        assert!(chart.get(Body::MeanNode).unwrap().is_retrograde());
+    }
+
+    #[test]
+    fn detects_mercury_station() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping detects_mercury_station: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        // Mercury stationed retrograde on 2023-04-21 around 08:35 UTC.
+        let birth = BirthData {
+            year: 2023,
+            month: 4,
+            day: 21,
+            hour: 8,
+            minute: 35,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        let chart = calculate_full_chart(&birth).expect("chart should compute");
+        let mercury = chart.get(Body::Mercury).unwrap();
+        assert_eq!(mercury.motion, MotionState::StationaryRetrograde);
+
+        let strict = ChartOptions {
+            station_threshold: 0.0,
+            ..ChartOptions::default()
+        };
+        let birth = BirthData { day: 25, ..birth };
+        let chart = calculate_full_chart_with(&birth, &strict).expect("chart should compute");
+        let mercury = chart.get(Body::Mercury).unwrap();
+        assert_eq!(mercury.motion, MotionState::Retrograde);
+
+        // Around the stations the direction follows the speed's sign, not the stationary
+        // state. Mercury stationed direct on 2023-05-15 around 03:17 UTC.
+        let at = |month, day, hour, minute| {
+            let birth = BirthData {
+                month,
+                day,
+                hour,
+                minute,
+                ..birth
+            };
+            let chart = calculate_full_chart(&birth).expect("chart should compute");
+            chart.get(Body::Mercury).unwrap().clone()
+        };
+        for (month, day, hour, minute, retrograde) in [
+            (4, 20, 8, 35, false),
+            (4, 21, 20, 0, true),
+            (4, 22, 8, 35, true),
+            (5, 14, 3, 17, true),
+            (5, 16, 3, 17, false),
+            (5, 15, 12, 0, false),
+        ] {
+            let mercury = at(month, day, hour, minute);
+            assert_eq!(
+                mercury.is_retrograde(),
+                retrograde,
+                "{}-{} speed {}",
+                month,
+                day,
+                mercury.longitude_speed
+            );
+            assert_eq!(mercury.longitude_speed < 0.0, retrograde);
+        }
+        assert_eq!(at(4, 21, 20, 0).motion, MotionState::StationaryRetrograde);
+        assert_eq!(at(5, 15, 12, 0).motion, MotionState::StationaryDirect);
+    }
+
+    #[test]
+    fn topocentric_moon_shows_parallax() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping topocentric_moon_shows_parallax: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 1990,
+            month: 7,
+            day: 15,
+            hour: 10,
+            minute: 30,
+            second: 0.0,
+            lat: -16.5,
+            lon: -68.15,
+            altitude_m: Some(3640.0),
+            calendar: Calendar::Gregorian,
+        };
+        let options = ChartOptions {
+            center: CenterMode::Topocentric,
+            ..ChartOptions::default()
+        };
+
+        let geo = calculate_full_chart(&birth).unwrap();
+        let topo = calculate_full_chart_with(&birth, &options).unwrap();
+        let geo_moon = geo.get(Body::Moon).unwrap().longitude;
+        let topo_moon = topo.get(Body::Moon).unwrap().longitude;
+        let parallax = (geo_moon - topo_moon + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(parallax.abs() > 0.01 && parallax.abs() < 1.1);
+
+        let bad = BirthData {
+            altitude_m: Some(f64::INFINITY),
+            calendar: Calendar::Gregorian,
+            ..birth
+        };
+        assert!(calculate_full_chart_with(&bad, &options).is_err());
+    }
+
+    #[test]
+    fn heliocentric_chart_excludes_earth_bound_points() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping heliocentric_chart_excludes_earth_bound_points: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 1990,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        let helio = ChartOptions {
+            center: CenterMode::Heliocentric,
+            ..ChartOptions::default()
+        };
+
+        let chart = calculate_full_chart_with(&birth, &helio).unwrap();
+        assert!(chart.get(Body::Sun).is_none());
+        assert!(chart.get(Body::MeanNode).is_none());
+        // Heliocentric planets never move backwards.
+        let mars = chart.get(Body::Mars).unwrap();
+        assert!(!mars.is_retrograde());
+        assert!(mars.distance > 1.3 && mars.distance < 1.7);
+
+        let err = calculate_body(&birth, Body::Sun, &helio).unwrap_err();
+        assert!(matches!(err, AstroError::InvalidInput(_)));
+
+        let bary = ChartOptions {
+            center: CenterMode::Barycentric,
+            ..ChartOptions::default()
+        };
+        let sun = calculate_body(&birth, Body::Sun, &bary).unwrap();
+        assert!(sun.distance < 0.02);
+    }
+
+    #[test]
+    fn reports_moshier_fallback_and_strict_mode() {
+        // A data directory without `.se1` files makes the C library fall back to Moshier.
+        let empty = std::env::temp_dir().join("astro-core-no-ephe");
+        std::fs::create_dir_all(&empty).unwrap();
+        let ephemeris = Ephemeris::new(empty.to_str().unwrap()).unwrap();
+        let birth = BirthData {
+            year: 1990,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+
+        let lenient = ChartOptions::default();
+        let mars = ephemeris.body(&birth, Body::Mars, &lenient).unwrap();
+        assert_eq!(mars.source, EphemerisSource::Moshier);
+
+        let strict = ChartOptions {
+            strict_ephemeris: true,
+            ..ChartOptions::default()
+        };
+        let err = ephemeris.body(&birth, Body::Mars, &strict).unwrap_err();
+        assert!(
+            matches!(err, AstroError::MissingEphemerisFile { ref file, .. } if file == "sepl_18.se1"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,800 @@
+use libc::c_int;
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, Weak},
+};
+
+use crate::{asteroids, chart, ffi, fixed_stars, houses, sidereal, AstroError, Calc};
+use crate::{julian, Calendar, HouseResult, HouseSystem, JulianDay, LeapSeconds, UtcDateTime};
+use crate::{
+    AsteroidNames, FixedStarPosition, StarCatalogue, StarConjunction, StarConjunctionOptions,
+    ASTEROID_NAMES_FILE, STAR_FILE,
+};
+use crate::{Ayanamsa, BirthData, Body, BodyPosition, ChartOptions, CoreChart, FullChart};
+
+/// Ephemeris used for calculations, set per `Ephemeris` context or process-wide with
+/// `set_ephemeris_backend`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum EphemerisBackend {
+    /// Built-in Moshier analytical ephemeris; needs no data files (precision about 1").
+    Moshier,
+    /// Swiss Ephemeris `.se1` files from the configured data path.
+    #[default]
+    Swiss,
+    /// A JPL DE file such as `de431.eph`, absolute or relative to the ephemeris path.
+    Jpl { file: String },
+}
+
+impl EphemerisBackend {
+    /// The source positions come from when the backend's data is available.
+    pub fn source(&self) -> EphemerisSource {
+        match self {
+            EphemerisBackend::Moshier => EphemerisSource::Moshier,
+            EphemerisBackend::Swiss => EphemerisSource::Swiss,
+            EphemerisBackend::Jpl { .. } => EphemerisSource::Jpl,
+        }
+    }
+
+    /// Ephemeris flag for calculations with this backend.
+    pub(crate) fn flag(&self) -> c_int {
+        match self {
+            EphemerisBackend::Moshier => ffi::SEFLG_MOSEPH,
+            EphemerisBackend::Swiss => ffi::SEFLG_SWIEPH,
+            EphemerisBackend::Jpl { .. } => ffi::SEFLG_JPLEPH,
+        }
+    }
+
+    /// Select the JPL file if needed.
+    fn install(&self) -> Result<(), AstroError> {
+        if let EphemerisBackend::Jpl { file } = self {
+            let c_file = CString::new(file.as_str())
+                .map_err(|_| AstroError::InvalidInput("JPL file name contains null byte".into()))?;
+            unsafe {
+                ffi::swe_set_jpl_file(c_file.as_ptr());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Process-wide lock around the Swiss Ephemeris C library. Its configuration (data path,
+/// JPL file, sidereal mode, observer position) and open files are global state (built with
+/// `TLSOFF`, see `build.rs`), so each calculation holds this lock while it installs its
+/// own settings and computes.
+///
+/// It also records the context whose settings are installed. Installing a data path flushes
+/// every open file and cache, so a session only does it when a different context takes over.
+static SWE_LOCK: Mutex<Installed> = Mutex::new(Installed(None));
+
+/// Context built from `set_ephe_path` and `set_ephemeris_backend`.
+static GLOBAL: Mutex<Option<Ephemeris>> = Mutex::new(None);
+
+/// The context whose settings the C library currently holds. `Weak` keeps the allocation
+/// alive, so a later context cannot reuse its address while it is recorded.
+struct Installed(Option<Weak<Settings>>);
+
+/// Override the Swiss Ephemeris data path. Defaults to the current directory when unset.
+///
+/// This configures the process-wide defaults used by the free functions such as
+/// `calculate_full_chart`; use an `Ephemeris` context for per-caller settings.
+pub fn set_ephe_path(path: &str) {
+    assert!(
+        !path.contains('\0'),
+        "ephemeris path must not contain interior null bytes"
+    );
+    update_global(|settings| settings.path = path.to_string());
+}
+
+/// Choose the ephemeris used by the free calculation functions. Defaults to Swiss `.se1` files.
+pub fn set_ephemeris_backend(backend: EphemerisBackend) {
+    update_global(|settings| settings.backend = backend);
+}
+
+/// Replace the process-wide context with a changed copy, under one lock so concurrent
+/// setters cannot overwrite each other's changes.
+fn update_global(change: impl FnOnce(&mut Settings)) {
+    let mut global = GLOBAL.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut settings = match global.as_ref() {
+        Some(current) => (*current.inner).clone(),
+        None => Settings::global_default(),
+    };
+    change(&mut settings);
+    *global = Some(Ephemeris::from_settings(settings));
+}
+
+fn lock_library() -> MutexGuard<'static, Installed> {
+    // A panic while installing settings leaves at worst a stale record; sessions compare
+    // it against their own context and reinstall, so poisoning can be ignored.
+    SWE_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Ephemeris configuration shared by a set of calculations.
+///
+/// Each method locks the C library, installs this context's data path and backend unless
+/// they are already in place, and computes, so contexts with different settings can be
+/// used from many threads.
+///
+/// Clones share one configuration. Dropping the last clone calls `swe_close`, releasing
+/// the open ephemeris files and cached data for every thread. Other live contexts are
+/// unaffected: they reinstall their settings on the next call.
+#[derive(Debug, Clone)]
+pub struct Ephemeris {
+    inner: Arc<Settings>,
+}
+
+#[derive(Debug, Clone)]
+struct Settings {
+    path: String,
+    backend: EphemerisBackend,
+    leap_seconds: Option<Arc<LeapSeconds>>,
+    delta_t: Option<f64>,
+    tidal_acceleration: Option<f64>,
+    asteroid_names: NamesCache,
+    /// False for the process-wide context behind the free functions. Replacing it with
+    /// `set_ephe_path` needs no close: the next session installs the new path, which
+    /// closes the old files.
+    close_on_drop: bool,
+}
+
+impl Ephemeris {
+    /// Context reading Swiss Ephemeris files from `path`.
+    pub fn new(path: &str) -> Result<Self, AstroError> {
+        if path.contains('\0') {
+            return Err(AstroError::InvalidInput(
+                "ephemeris path contains null byte".into(),
+            ));
+        }
+        Ok(Ephemeris::from_settings(Settings {
+            path: path.to_string(),
+            backend: EphemerisBackend::default(),
+            leap_seconds: None,
+            delta_t: None,
+            tidal_acceleration: None,
+            asteroid_names: NamesCache::default(),
+            close_on_drop: true,
+        }))
+    }
+
+    fn from_settings(settings: Settings) -> Self {
+        Ephemeris {
+            inner: Arc::new(settings),
+        }
+    }
+
+    /// A context with modified settings. The settings move over when this is the only
+    /// clone, so the old configuration is not closed.
+    fn configure(self, change: impl FnOnce(&mut Settings)) -> Self {
+        let mut settings = Arc::try_unwrap(self.inner).unwrap_or_else(|shared| (*shared).clone());
+        change(&mut settings);
+        Ephemeris::from_settings(settings)
+    }
+
+    /// Use `backend` instead of the Swiss `.se1` files.
+    pub fn with_backend(self, backend: EphemerisBackend) -> Self {
+        self.configure(|s| s.backend = backend)
+    }
+
+    /// Convert UTC with this leap-second table instead of the one built into the C library,
+    /// both in `julian_day` and back in `to_utc`.
+    pub fn with_leap_seconds(self, leap_seconds: LeapSeconds) -> Self {
+        self.configure(|s| s.leap_seconds = Some(Arc::new(leap_seconds)))
+    }
+
+    /// Use a fixed Delta T (TT - UT) in seconds for every date instead of the Swiss
+    /// Ephemeris model (`swe_set_delta_t_userdef`).
+    pub fn with_delta_t(self, seconds: f64) -> Self {
+        self.configure(|s| s.delta_t = Some(seconds))
+    }
+
+    /// Tidal acceleration of the Moon in arcsec/century², which shapes Delta T for
+    /// historical dates (`swe_set_tid_acc`). Defaults to the value matching the ephemeris.
+    pub fn with_tidal_acceleration(self, arcsec_per_cy2: f64) -> Self {
+        self.configure(|s| s.tidal_acceleration = Some(arcsec_per_cy2))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.inner.path
+    }
+
+    pub fn backend(&self) -> &EphemerisBackend {
+        &self.inner.backend
+    }
+
+    /// Context built from `set_ephe_path` and `set_ephemeris_backend`.
+    pub(crate) fn global() -> Self {
+        GLOBAL
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert_with(|| Ephemeris::from_settings(Settings::global_default()))
+            .clone()
+    }
+
+    /// Calculate the Sun, Moon, and Ascendant signs.
+    pub fn core_chart(&self, birth: &BirthData) -> Result<CoreChart, AstroError> {
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        crate::core_chart(session.ephe_flag, jd, birth)
+    }
+
+    /// Calculate positions for every body in `Body::ALL` supported by `options.center`.
+    pub fn full_chart(
+        &self,
+        birth: &BirthData,
+        options: &ChartOptions,
+    ) -> Result<FullChart, AstroError> {
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        chart::full_chart_at(session.ephe_flag, jd, Some(birth), options)
+    }
+
+    /// Calculate the position of a single body.
+    pub fn body(
+        &self,
+        birth: &BirthData,
+        body: Body,
+        options: &ChartOptions,
+    ) -> Result<BodyPosition, AstroError> {
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        chart::single_body(session.ephe_flag, jd, Some(birth), body, options)
+    }
+
+    /// Julian day of the birth moment, converted from UTC with this context's settings.
+    pub fn julian_day(&self, birth: &BirthData) -> Result<JulianDay, AstroError> {
+        self.session()?.julian_day(birth)
+    }
+
+    /// Julian day from a UT1 Julian day number, adding this context's Delta T for TT.
+    pub fn julian_day_from_ut(&self, ut: f64) -> Result<JulianDay, AstroError> {
+        let session = self.session()?;
+        julian::from_ut(ut, session.ephe_flag)
+    }
+
+    /// Julian day from a TT Julian day number, subtracting this context's Delta T for UT1.
+    pub fn julian_day_from_tt(&self, tt: f64) -> Result<JulianDay, AstroError> {
+        let session = self.session()?;
+        julian::from_tt(tt, session.ephe_flag)
+    }
+
+    /// UTC date and time of a moment, using this context's leap-second table.
+    pub fn to_utc(&self, jd: JulianDay, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        self.session()?.to_utc(jd, calendar)
+    }
+
+    /// Like `to_utc`, deriving TT from the UT1 value with this context's Delta T.
+    pub fn ut_to_utc(&self, jd: JulianDay, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        self.session()?.ut_to_utc(jd, calendar)
+    }
+
+    /// Like `full_chart` at a precomputed moment. Topocentric charts need an observer
+    /// and return `InvalidInput`; use `full_chart` for them.
+    pub fn full_chart_at(
+        &self,
+        jd: JulianDay,
+        options: &ChartOptions,
+    ) -> Result<FullChart, AstroError> {
+        let session = self.session()?;
+        chart::full_chart_at(session.ephe_flag, jd, None, options)
+    }
+
+    /// Like `body` at a precomputed moment; see `full_chart_at`.
+    pub fn body_at(
+        &self,
+        jd: JulianDay,
+        body: Body,
+        options: &ChartOptions,
+    ) -> Result<BodyPosition, AstroError> {
+        let session = self.session()?;
+        chart::single_body(session.ephe_flag, jd, None, body, options)
+    }
+
+    /// Calculate house cusps and angles in the given system.
+    pub fn houses(
+        &self,
+        birth: &BirthData,
+        system: HouseSystem,
+        options: &ChartOptions,
+    ) -> Result<HouseResult, AstroError> {
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        houses::houses(session.ephe_flag, jd, birth, system, options)
+    }
+
+    /// Ayanamsa value in degrees at the birth time, including nutation.
+    pub fn ayanamsa(&self, birth: &BirthData, ayanamsa: Ayanamsa) -> Result<f64, AstroError> {
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        sidereal::ayanamsa_ut(jd.ut(), ayanamsa, session.ephe_flag)
+    }
+
+    /// Position of a numbered asteroid; see `calculate_asteroid`.
+    pub fn asteroid(
+        &self,
+        birth: &BirthData,
+        number: u32,
+        options: &ChartOptions,
+    ) -> Result<BodyPosition, AstroError> {
+        asteroids::asteroid_body(number, |body| self.body(birth, body, options))
+    }
+
+    /// Position of the asteroid with this name in `seasnam.txt`, ignoring case.
+    pub fn asteroid_by_name(
+        &self,
+        birth: &BirthData,
+        name: &str,
+        options: &ChartOptions,
+    ) -> Result<BodyPosition, AstroError> {
+        let number = self.asteroid_names()?.number(name).ok_or_else(|| {
+            AstroError::UnknownBody(format!(
+                "no asteroid named '{}' in {}",
+                name.trim(),
+                ASTEROID_NAMES_FILE
+            ))
+        })?;
+        self.asteroid(birth, number, options)
+    }
+
+    /// The `seasnam.txt` index, read on first use and shared by this context's clones.
+    pub fn asteroid_names(&self) -> Result<Arc<AsteroidNames>, AstroError> {
+        let cache = &self.inner.asteroid_names.0;
+        if let Some(names) = cache.get() {
+            return Ok(Arc::clone(names));
+        }
+        let names = AsteroidNames::load(asteroids::names_file(self.path())?)?;
+        Ok(Arc::clone(cache.get_or_init(|| Arc::new(names))))
+    }
+
+    /// Position and magnitude of a fixed star by name ("Regulus") or nomenclature ("alLeo").
+    /// Topocentric options return `InvalidInput`, as for `body_at`.
+    pub fn fixed_star(
+        &self,
+        name: &str,
+        jd: JulianDay,
+        options: &ChartOptions,
+    ) -> Result<FixedStarPosition, AstroError> {
+        let session = self.session()?;
+        fixed_stars::fixed_star_at(session.ephe_flag, name, jd, None, options)
+    }
+
+    /// Fixed stars within orb of the natal bodies and angles.
+    pub fn star_conjunctions(
+        &self,
+        birth: &BirthData,
+        options: &ChartOptions,
+        stars: &StarConjunctionOptions,
+    ) -> Result<Vec<StarConjunction>, AstroError> {
+        let catalogue = self.star_catalogue()?;
+        let session = self.session()?;
+        let jd = session.julian_day(birth)?;
+        fixed_stars::star_conjunctions(session.ephe_flag, jd, birth, &catalogue, options, stars)
+    }
+
+    /// Read `sefstars.txt` from the first directory of this context's path that has it.
+    pub fn star_catalogue(&self) -> Result<StarCatalogue, AstroError> {
+        let path = find_in_ephe_path(self.path(), STAR_FILE).ok_or_else(|| {
+            AstroError::MissingEphemerisFile {
+                file: STAR_FILE.to_string(),
+                message: format!("{} not found in PATH '{}'", STAR_FILE, self.path()),
+            }
+        })?;
+        StarCatalogue::load(path)
+    }
+
+    /// Lock the C library and install this context's path, backend, and time settings
+    /// if another context's are in place.
+    pub(crate) fn session(&self) -> Result<Session<'_>, AstroError> {
+        let mut guard = lock_library();
+        let installed = guard
+            .0
+            .as_ref()
+            .is_some_and(|current| current.as_ptr() == Arc::as_ptr(&self.inner));
+        if !installed {
+            guard.0 = None;
+            self.install()?;
+            guard.0 = Some(Arc::downgrade(&self.inner));
+        }
+        Ok(Session {
+            _guard: guard,
+            context: self,
+            ephe_flag: self.inner.backend.flag(),
+        })
+    }
+
+    /// Push the settings into the C library. Callers hold the library lock.
+    fn install(&self) -> Result<(), AstroError> {
+        let settings = &*self.inner;
+        let c_path = CString::new(settings.path.as_str())
+            .map_err(|_| AstroError::InvalidInput("ephemeris path contains null byte".into()))?;
+        unsafe {
+            ffi::swe_set_ephe_path(c_path.as_ptr());
+        }
+        settings.backend.install()?;
+        let delta_t = settings
+            .delta_t
+            .map_or(ffi::SE_DELTAT_AUTOMATIC, |seconds| seconds / 86_400.0);
+        let tidal_acceleration = settings
+            .tidal_acceleration
+            .unwrap_or(ffi::SE_TIDAL_AUTOMATIC);
+        unsafe {
+            ffi::swe_set_delta_t_userdef(delta_t);
+            ffi::swe_set_tid_acc(tidal_acceleration);
+        }
+        Ok(())
+    }
+}
+
+/// Asteroid name index loaded on first use. Copied settings start empty, since
+/// `set_ephe_path` may point the copy at another directory.
+#[derive(Debug, Default)]
+struct NamesCache(OnceLock<Arc<AsteroidNames>>);
+
+impl Clone for NamesCache {
+    fn clone(&self) -> Self {
+        NamesCache::default()
+    }
+}
+
+impl Settings {
+    /// Settings of the process-wide context before `set_ephe_path` or
+    /// `set_ephemeris_backend` change them.
+    fn global_default() -> Self {
+        Settings {
+            path: String::new(),
+            backend: EphemerisBackend::default(),
+            leap_seconds: None,
+            delta_t: None,
+            tidal_acceleration: None,
+            asteroid_names: NamesCache::default(),
+            close_on_drop: false,
+        }
+    }
+}
+
+impl Drop for Settings {
+    // Runs once the last clone of a context is gone.
+    fn drop(&mut self) {
+        if !self.close_on_drop {
+            return;
+        }
+        let mut installed = lock_library();
+        unsafe {
+            ffi::swe_close();
+        }
+        // Everything was reset, including whichever context was installed.
+        installed.0 = None;
+    }
+}
+
+/// Exclusive access to the C library, configured for one context.
+pub(crate) struct Session<'a> {
+    _guard: MutexGuard<'static, Installed>,
+    context: &'a Ephemeris,
+    /// `SEFLG_SWIEPH`, `SEFLG_MOSEPH`, or `SEFLG_JPLEPH`.
+    pub ephe_flag: c_int,
+}
+
+impl Session<'_> {
+    /// Julian day of the birth moment, using the context's leap-second table.
+    pub fn julian_day(&self, birth: &BirthData) -> Result<JulianDay, AstroError> {
+        let leap_seconds = self.context.inner.leap_seconds.as_deref();
+        julian::julian_day(birth, leap_seconds, self.ephe_flag)
+    }
+
+    /// UTC date and time of a moment, using the context's leap-second table.
+    pub fn to_utc(&self, jd: JulianDay, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        let leap_seconds = self.context.inner.leap_seconds.as_deref();
+        julian::to_utc(jd, calendar, leap_seconds, self.ephe_flag)
+    }
+
+    /// Like `to_utc`, converting from the UT1 value.
+    pub fn ut_to_utc(&self, jd: JulianDay, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        let leap_seconds = self.context.inner.leap_seconds.as_deref();
+        julian::ut_to_utc(jd, calendar, leap_seconds, self.ephe_flag)
+    }
+}
+
+/// Ephemeris that actually produced a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EphemerisSource {
+    /// Swiss Ephemeris `.se1` data files.
+    Swiss,
+    /// Built-in Moshier analytical ephemeris; used when `.se1` files are missing.
+    Moshier,
+    /// JPL DE ephemeris file.
+    Jpl,
+}
+
+impl EphemerisSource {
+    pub(crate) fn from_flags(flags: c_int) -> Option<EphemerisSource> {
+        match flags & ffi::SEFLG_EPHMASK {
+            ffi::SEFLG_JPLEPH => Some(EphemerisSource::Jpl),
+            ffi::SEFLG_SWIEPH => Some(EphemerisSource::Swiss),
+            ffi::SEFLG_MOSEPH => Some(EphemerisSource::Moshier),
+            _ => None,
+        }
+    }
+}
+
+/// Determine which ephemeris answered a `swe_calc_ut` call. With `strict`, a silent
+/// fallback away from the ephemeris requested in `iflag` becomes an error.
+pub(crate) fn checked_source(
+    iflag: c_int,
+    calc: &Calc,
+    strict: bool,
+) -> Result<EphemerisSource, AstroError> {
+    let requested = EphemerisSource::from_flags(iflag);
+    let used = EphemerisSource::from_flags(calc.retflag)
+        .or(requested)
+        .unwrap_or(EphemerisSource::Swiss);
+    if let Some(requested) = requested.filter(|&r| strict && r != used) {
+        let message = calc.warning.clone().unwrap_or_default();
+        return Err(match AstroError::from_message(message.clone()) {
+            err @ AstroError::MissingEphemerisFile { .. } => err,
+            _ => AstroError::EphemerisError(
+                format!(
+                    "{:?} ephemeris used instead of {:?}; {}",
+                    used, requested, message
+                )
+                .trim_end_matches([' ', ';'])
+                .to_string(),
+            ),
+        });
+    }
+    Ok(used)
+}
+
+// PATH_SEPARATOR in sweodef.h: Windows paths keep the colon after a drive letter.
+#[cfg(windows)]
+const PATH_SEPARATORS: &[char] = &[';'];
+#[cfg(not(windows))]
+const PATH_SEPARATORS: &[char] = &[';', ':'];
+
+/// First directory in a Swiss Ephemeris path list that contains `file`.
+pub(crate) fn find_in_ephe_path(ephe_path: &str, file: &str) -> Option<PathBuf> {
+    let dirs: Vec<&str> = ephe_path.split(PATH_SEPARATORS).collect();
+    dirs.iter()
+        .map(|dir| {
+            if dir.is_empty() {
+                Path::new(".")
+            } else {
+                Path::new(dir)
+            }
+        })
+        .map(|dir| dir.join(file))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Calendar;
+    use std::path::Path;
+    use std::thread;
+
+    fn birth() -> BirthData {
+        BirthData {
+            year: 1990,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        }
+    }
+
+    #[test]
+    fn searches_ephe_path_lists() {
+        let dir = std::env::temp_dir().join("astro-core-ephe-path");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(STAR_FILE), "").unwrap();
+        let dir = dir.to_str().unwrap();
+
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        let list = format!("does-not-exist{}{}", separator, dir);
+        assert_eq!(
+            find_in_ephe_path(&list, STAR_FILE),
+            Some(Path::new(dir).join(STAR_FILE))
+        );
+        assert_eq!(find_in_ephe_path("does-not-exist", STAR_FILE), None);
+        if cfg!(windows) {
+            // The drive letter's colon is part of the directory.
+            assert_eq!(
+                find_in_ephe_path(&format!("C:\\does-not-exist;{}", dir), STAR_FILE),
+                Some(Path::new(dir).join(STAR_FILE))
+            );
+        }
+    }
+
+    #[test]
+    fn backends_select_ephemeris() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping backends_select_ephemeris: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let options = ChartOptions {
+            strict_ephemeris: true,
+            ..ChartOptions::default()
+        };
+        let swiss = Ephemeris::new(ephe_path).unwrap();
+        let moshier = swiss.clone().with_backend(EphemerisBackend::Moshier);
+
+        let swiss_mars = swiss.body(&birth(), Body::Mars, &options).unwrap();
+        let moshier_mars = moshier.body(&birth(), Body::Mars, &options).unwrap();
+        assert_eq!(swiss_mars.source, EphemerisSource::Swiss);
+        assert_eq!(moshier_mars.source, EphemerisSource::Moshier);
+        // Moshier agrees with the Swiss files to within a few arcseconds.
+        assert!((swiss_mars.longitude - moshier_mars.longitude).abs() < 0.01);
+
+        // No DE file ships with the crate: lenient mode falls back, strict mode refuses.
+        let jpl = swiss.clone().with_backend(EphemerisBackend::Jpl {
+            file: "de431.eph".to_string(),
+        });
+        let fallback = jpl
+            .body(&birth(), Body::Mars, &ChartOptions::default())
+            .unwrap();
+        assert_ne!(fallback.source, EphemerisSource::Jpl);
+        // Swiss Ephemeris names the missing file only on calls after the first one since the
+        // JPL file was selected, so either error may come back.
+        let err = jpl.body(&birth(), Body::Mars, &options).unwrap_err();
+        assert!(
+            matches!(err, AstroError::MissingEphemerisFile { ref file, .. } if file == "de431.eph")
+                || err.to_string().contains("instead of Jpl"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn dropping_context_allows_switching_paths() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping dropping_context_allows_switching_paths: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let empty = std::env::temp_dir().join("astro-core-closed-ephe");
+        std::fs::create_dir_all(&empty).unwrap();
+        let options = ChartOptions::default();
+
+        let swiss = Ephemeris::new(ephe_path).unwrap();
+        let before = swiss.body(&birth(), Body::Mars, &options).unwrap();
+        assert_eq!(before.source, EphemerisSource::Swiss);
+        drop(swiss);
+
+        // With the files closed, an empty directory leaves only the Moshier fallback.
+        let missing = Ephemeris::new(empty.to_str().unwrap()).unwrap();
+        let fallback = missing.body(&birth(), Body::Mars, &options).unwrap();
+        assert_eq!(fallback.source, EphemerisSource::Moshier);
+        drop(missing);
+
+        let reopened = Ephemeris::new(ephe_path).unwrap();
+        let after = reopened.body(&birth(), Body::Mars, &options).unwrap();
+        assert_eq!(after.source, EphemerisSource::Swiss);
+        assert_eq!(after.longitude, before.longitude);
+    }
+
+    #[test]
+    fn sessions_keep_files_open_for_the_same_context() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping sessions_keep_files_open_for_the_same_context: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let ephemeris = Ephemeris::new(ephe_path).unwrap();
+        // Installing a path forgets the open planet file (file number 0), so it is still
+        // known only if the second session skipped the reinstall. Other tests may take over
+        // the library in between, hence the retries.
+        let kept_open = (0..20).any(|_| {
+            let session = ephemeris.session().unwrap();
+            crate::calc_ut(2_447_892.5, ffi::SE_MARS, session.ephe_flag).unwrap();
+            drop(session);
+            let _session = ephemeris.session().unwrap();
+            let (mut start, mut end, mut denum) = (0.0, 0.0, 0);
+            let name =
+                unsafe { ffi::swe_get_current_file_data(0, &mut start, &mut end, &mut denum) };
+            !name.is_null()
+        });
+        assert!(kept_open);
+    }
+
+    // Open file descriptors of this process pointing into `dir`.
+    #[cfg(target_os = "linux")]
+    fn open_files_in(dir: &Path) -> usize {
+        std::fs::read_dir("/proc/self/fd")
+            .unwrap()
+            .filter_map(|entry| std::fs::read_link(entry.ok()?.path()).ok())
+            .filter(|target| target.starts_with(dir))
+            .count()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn dropping_last_clone_closes_files() {
+        let ephe_path = Path::new("src/swisseph/ephe");
+        if !ephe_path.exists() {
+            eprintln!(
+                "skipping dropping_last_clone_closes_files: missing ephemeris data at {}",
+                ephe_path.display()
+            );
+            return;
+        }
+        // A directory of its own, so no other test opens files there.
+        let dir = std::env::temp_dir().join("astro-core-close-on-drop");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy(ephe_path.join("sepl_18.se1"), dir.join("sepl_18.se1")).unwrap();
+
+        let ephemeris = Ephemeris::new(dir.to_str().unwrap()).unwrap();
+        let session = ephemeris.session().unwrap();
+        let mars = crate::calc_ut(2_447_892.5, ffi::SE_MARS, session.ephe_flag).unwrap();
+        assert_eq!(
+            EphemerisSource::from_flags(mars.retflag),
+            Some(EphemerisSource::Swiss)
+        );
+        assert!(open_files_in(&dir) > 0);
+        // Dropping a clone leaves the shared configuration and its files alone.
+        drop(ephemeris.clone());
+        assert!(open_files_in(&dir) > 0);
+        drop(session);
+
+        // The last clone closes the file, even when dropped on a different thread.
+        thread::spawn(move || drop(ephemeris)).join().unwrap();
+        assert_eq!(open_files_in(&dir), 0);
+    }
+
+    #[test]
+    fn contexts_are_isolated_across_threads() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping contexts_are_isolated_across_threads: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let empty = std::env::temp_dir().join("astro-core-empty-ephe");
+        std::fs::create_dir_all(&empty).unwrap();
+        let contexts = [
+            (Ephemeris::new(ephe_path).unwrap(), EphemerisSource::Swiss),
+            (
+                Ephemeris::new(empty.to_str().unwrap()).unwrap(),
+                EphemerisSource::Moshier,
+            ),
+            (
+                Ephemeris::new(ephe_path)
+                    .unwrap()
+                    .with_backend(EphemerisBackend::Moshier),
+                EphemerisSource::Moshier,
+            ),
+        ];
+
+        let handles: Vec<_> = contexts
+            .into_iter()
+            .map(|(ephemeris, expected)| {
+                thread::spawn(move || {
+                    for _ in 0..20 {
+                        let mars = ephemeris
+                            .body(&birth(), Body::Mars, &ChartOptions::default())
+                            .unwrap();
+                        assert_eq!(mars.source, expected);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
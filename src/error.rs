@@ -0,0 +1,152 @@
+use libc::c_char;
+use thiserror::Error;
+
+use crate::HouseSystem;
+
+#[derive(Debug, Error)]
+pub enum AstroError {
+    /// Any Swiss Ephemeris failure not covered by a more specific variant.
+    #[error("Swiss Ephemeris error: {0}")]
+    EphemerisError(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// A required `.se1` (or JPL) file is not in the ephemeris path.
+    #[error("Ephemeris file not found: {file} ({message})")]
+    MissingEphemerisFile { file: String, message: String },
+
+    /// The date lies outside the range covered by the available ephemeris.
+    #[error("Date outside ephemeris range: {0}")]
+    DateOutOfRange(String),
+
+    #[error("Unknown body: {0}")]
+    UnknownBody(String),
+
+    /// The requested house system cannot be computed, e.g. Placidus inside the polar circle.
+    #[error("{system:?} houses unavailable: {reason}")]
+    HouseSystemFailure { system: HouseSystem, reason: String },
+
+    #[error("Invalid coordinates: {0}")]
+    InvalidCoordinates(String),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    /// The IANA time-zone name is not in the embedded tz database.
+    #[error("Unknown time zone: {0}")]
+    UnknownTimeZone(String),
+
+    /// A local time that occurs twice because clocks were set back (e.g. end of DST).
+    /// Offsets are in hours east of UTC.
+    #[error(
+        "Ambiguous local time {time} in {zone}: UTC offset {earlier_offset:+} h or {later_offset:+} h"
+    )]
+    AmbiguousLocalTime {
+        time: String,
+        zone: String,
+        earlier_offset: f64,
+        later_offset: f64,
+    },
+
+    /// A local time skipped because clocks sprang forward (e.g. start of DST).
+    /// Offsets are in hours east of UTC.
+    #[error(
+        "Nonexistent local time {time} in {zone}: UTC offset jumps from {offset_before:+} h to {offset_after:+} h"
+    )]
+    NonexistentLocalTime {
+        time: String,
+        zone: String,
+        offset_before: f64,
+        offset_after: f64,
+    },
+}
+
+impl AstroError {
+    /// Classify a Swiss Ephemeris error message (the `serr` buffer).
+    pub(crate) fn from_message(message: String) -> AstroError {
+        if let Some(file) = missing_file(&message) {
+            return AstroError::MissingEphemerisFile { file, message };
+        }
+        let lower = message.to_lowercase();
+        if lower.contains("lower limit")
+            || lower.contains("upper limit")
+            || (lower.contains("outside") && lower.contains("range"))
+        {
+            AstroError::DateOutOfRange(message)
+        } else if lower.contains("illegal planet number")
+            || lower.contains("could not find star")
+            || (lower.starts_with("star ") && lower.ends_with("not found"))
+        {
+            AstroError::UnknownBody(message)
+        } else if lower.starts_with("invalid date") || lower.starts_with("invalid time") {
+            AstroError::InvalidDate(message)
+        } else {
+            AstroError::EphemerisError(message)
+        }
+    }
+}
+
+/// Build an error from a Swiss Ephemeris `serr` buffer after a negative return code.
+pub(crate) fn ephemeris_error(serr: &[c_char]) -> AstroError {
+    AstroError::from_message(error_string(serr))
+}
+
+pub(crate) fn error_string(buf: &[c_char]) -> String {
+    let nul = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    let bytes: Vec<u8> = buf[..nul].iter().map(|&c| c as u8).collect();
+    if bytes.is_empty() {
+        "unknown Swiss Ephemeris error".to_string()
+    } else {
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+// Swiss Ephemeris reports missing files as "SwissEph file 'sepl_18.se1' not found in PATH ...".
+fn missing_file(message: &str) -> Option<String> {
+    let marker = "file '";
+    let start = message.find(marker)? + marker.len();
+    let rest = &message[start..];
+    let end = rest.find('\'')?;
+    if !rest[end..].starts_with("' not found") {
+        return None;
+    }
+    Some(rest[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_swiss_ephemeris_messages() {
+        let missing = AstroError::from_message(
+            "SwissEph file 'ast0/se00433.se1' not found in PATH '/tmp/ephe'".to_string(),
+        );
+        assert!(matches!(
+            missing,
+            AstroError::MissingEphemerisFile { ref file, .. } if file == "ast0/se00433.se1"
+        ));
+
+        let range = AstroError::from_message(
+            "jd 9000000.000000 outside Moshier planet range -3026604.50 .. 7857139.50 ".into(),
+        );
+        assert!(matches!(range, AstroError::DateOutOfRange(_)));
+
+        let body = AstroError::from_message("illegal planet number 99999.".into());
+        assert!(matches!(body, AstroError::UnknownBody(_)));
+        let star = AstroError::from_message("star Vulcan not found".into());
+        assert!(matches!(star, AstroError::UnknownBody(_)));
+        let star = AstroError::from_message(
+            "error, swe_fixstar(): could not find star name vulcan".into(),
+        );
+        assert!(matches!(star, AstroError::UnknownBody(_)));
+
+        let date =
+            AstroError::from_message("invalid date: year = 1990, month = 13, day = 1".into());
+        assert!(matches!(date, AstroError::InvalidDate(_)));
+
+        let other = AstroError::from_message("something else".into());
+        assert!(matches!(other, AstroError::EphemerisError(_)));
+    }
+}
@@ -0,0 +1,204 @@
+use libc::{c_char, c_double, c_int};
+
+pub const SE_SUN: c_int = 0;
+pub const SE_MOON: c_int = 1;
+pub const SE_MERCURY: c_int = 2;
+pub const SE_VENUS: c_int = 3;
+pub const SE_MARS: c_int = 4;
+pub const SE_JUPITER: c_int = 5;
+pub const SE_SATURN: c_int = 6;
+pub const SE_URANUS: c_int = 7;
+pub const SE_NEPTUNE: c_int = 8;
+pub const SE_PLUTO: c_int = 9;
+pub const SE_MEAN_NODE: c_int = 10;
+pub const SE_TRUE_NODE: c_int = 11;
+pub const SE_CHIRON: c_int = 15;
+#[cfg(test)]
+pub const SE_VESTA: c_int = 20;
+pub const SE_AST_OFFSET: c_int = 10000;
+pub const SE_ASC: usize = 0;
+pub const SE_MC: usize = 1;
+pub const SE_ARMC: usize = 2;
+pub const SE_VERTEX: usize = 3;
+pub const SE_EQUASC: usize = 4;
+pub const SE_COASC1: usize = 5;
+pub const SE_COASC2: usize = 6;
+pub const SE_POLASC: usize = 7;
+pub const SE_JUL_CAL: c_int = 0;
+pub const SE_GREG_CAL: c_int = 1;
+pub const SEFLG_JPLEPH: c_int = 1;
+pub const SEFLG_SWIEPH: c_int = 2;
+pub const SEFLG_MOSEPH: c_int = 4;
+pub const SEFLG_EPHMASK: c_int = SEFLG_JPLEPH | SEFLG_SWIEPH | SEFLG_MOSEPH;
+pub const SEFLG_SPEED: c_int = 256;
+#[cfg(test)]
+pub const SEFLG_NONUT: c_int = 64;
+pub const SEFLG_HELCTR: c_int = 8;
+pub const SEFLG_EQUATORIAL: c_int = 2 * 1024;
+pub const SEFLG_BARYCTR: c_int = 16 * 1024;
+pub const SEFLG_TOPOCTR: c_int = 32 * 1024;
+pub const SEFLG_SIDEREAL: c_int = 64 * 1024;
+pub const SE_SIDM_USER: c_int = 255;
+pub const SE_SIDBIT_USER_UT: c_int = 1024;
+pub const SE_SPLIT_DEG_ROUND_SEC: c_int = 1;
+pub const SE_SPLIT_DEG_ROUND_MIN: c_int = 2;
+pub const SE_SPLIT_DEG_ROUND_DEG: c_int = 4;
+pub const SE_SPLIT_DEG_ZODIACAL: c_int = 8;
+pub const SE_SPLIT_DEG_KEEP_SIGN: c_int = 16;
+pub const SE_SPLIT_DEG_KEEP_DEG: c_int = 32;
+pub const SE_TIDAL_AUTOMATIC: c_double = 999_999.0;
+pub const SE_DELTAT_AUTOMATIC: c_double = -1e-10;
+pub const AS_MAXCH: usize = 256;
+pub const SE_MAX_STNAME: usize = 256;
+
+extern "C" {
+    pub fn swe_set_ephe_path(path: *const c_char);
+
+    pub fn swe_utc_to_jd(
+        year: c_int,
+        month: c_int,
+        day: c_int,
+        hour: c_int,
+        minute: c_int,
+        second: c_double,
+        gregflag: c_int,
+        dret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    pub fn swe_calc_ut(
+        tjd_ut: c_double,
+        ipl: c_int,
+        iflag: c_int,
+        xx: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    pub fn swe_fixstar2_ut(
+        star: *mut c_char,
+        tjd_ut: c_double,
+        iflag: c_int,
+        xx: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    pub fn swe_fixstar2_mag(star: *mut c_char, mag: *mut c_double, serr: *mut c_char) -> c_int;
+
+    pub fn swe_houses_ex2(
+        tjd_ut: c_double,
+        iflag: c_int,
+        geolat: c_double,
+        geolon: c_double,
+        hsys: c_int,
+        cusps: *mut c_double,
+        ascmc: *mut c_double,
+        cusp_speed: *mut c_double,
+        ascmc_speed: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    pub fn swe_set_sid_mode(sid_mode: c_int, t0: c_double, ayan_t0: c_double);
+
+    pub fn swe_get_ayanamsa_ex_ut(
+        tjd_ut: c_double,
+        iflag: c_int,
+        daya: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    pub fn swe_get_ayanamsa_name(isidmode: c_int) -> *const c_char;
+
+    pub fn swe_set_topo(geolon: c_double, geolat: c_double, geoalt: c_double);
+
+    pub fn swe_set_jpl_file(fname: *const c_char);
+
+    pub fn swe_close();
+
+    #[cfg(test)]
+    pub fn swe_get_current_file_data(
+        ifno: c_int,
+        tfstart: *mut c_double,
+        tfend: *mut c_double,
+        denum: *mut c_int,
+    ) -> *const c_char;
+
+    pub fn swe_julday(
+        year: c_int,
+        month: c_int,
+        day: c_int,
+        hour: c_double,
+        gregflag: c_int,
+    ) -> c_double;
+
+    pub fn swe_revjul(
+        jd: c_double,
+        gregflag: c_int,
+        year: *mut c_int,
+        month: *mut c_int,
+        day: *mut c_int,
+        hour: *mut c_double,
+    );
+
+    pub fn swe_jdet_to_utc(
+        tjd_et: c_double,
+        gregflag: c_int,
+        year: *mut c_int,
+        month: *mut c_int,
+        day: *mut c_int,
+        hour: *mut c_int,
+        minute: *mut c_int,
+        second: *mut c_double,
+    );
+
+    pub fn swe_jdut1_to_utc(
+        tjd_ut: c_double,
+        gregflag: c_int,
+        year: *mut c_int,
+        month: *mut c_int,
+        day: *mut c_int,
+        hour: *mut c_int,
+        minute: *mut c_int,
+        second: *mut c_double,
+    );
+
+    pub fn swe_deltat_ex(tjd: c_double, iflag: c_int, serr: *mut c_char) -> c_double;
+
+    pub fn swe_set_delta_t_userdef(dt: c_double);
+
+    pub fn swe_set_tid_acc(t_acc: c_double);
+
+    pub fn swe_date_conversion(
+        year: c_int,
+        month: c_int,
+        day: c_int,
+        utime: c_double,
+        calendar: c_char,
+        tjd: *mut c_double,
+    ) -> c_int;
+
+    pub fn swe_utc_time_zone(
+        iyear: c_int,
+        imonth: c_int,
+        iday: c_int,
+        ihour: c_int,
+        imin: c_int,
+        dsec: c_double,
+        d_timezone: c_double,
+        iyear_out: *mut c_int,
+        imonth_out: *mut c_int,
+        iday_out: *mut c_int,
+        ihour_out: *mut c_int,
+        imin_out: *mut c_int,
+        dsec_out: *mut c_double,
+    );
+
+    pub fn swe_split_deg(
+        ddeg: c_double,
+        roundflag: c_int,
+        ideg: *mut c_int,
+        imin: *mut c_int,
+        isec: *mut c_int,
+        dsecfr: *mut c_double,
+        isgn: *mut c_int,
+    );
+}
@@ -0,0 +1,575 @@
+use libc::{c_char, c_int};
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+
+use crate::ephemeris::checked_source;
+use crate::error::{ephemeris_error, error_string};
+use crate::{chart, ffi, houses, AstroError, BirthData, Body, Calc, ChartOptions, Ephemeris};
+use crate::{EphemerisSource, HouseSystem, JulianDay, Sign};
+
+/// Name of the Swiss Ephemeris fixed-star catalogue in the ephemeris directory.
+pub const STAR_FILE: &str = "sefstars.txt";
+
+/// Position of a fixed star from `sefstars.txt`, with proper motion and precession applied.
+#[derive(Debug, Clone)]
+pub struct FixedStarPosition {
+    pub name: String,            // traditional name, e.g. "Regulus"
+    pub designation: String,     // Bayer/Flamsteed nomenclature, e.g. "alLeo"
+    pub longitude: f64,          // ecliptic longitude in degrees, 0-360
+    pub latitude: f64,           // ecliptic latitude in degrees
+    pub distance: f64,           // distance in AU
+    pub longitude_speed: f64,    // degrees/day
+    pub magnitude: f64,          // visual magnitude; smaller is brighter
+    pub source: EphemerisSource, // ephemeris used for the Earth's position
+    pub sign: Sign,
+    pub sign_degree: f64, // degrees within the sign, 0-30
+}
+
+/// Calculate a fixed star by traditional name ("Spica") or nomenclature ("alVir").
+pub fn fixed_star(name: &str, jd: JulianDay) -> Result<FixedStarPosition, AstroError> {
+    Ephemeris::global().fixed_star(name, jd, &ChartOptions::default())
+}
+
+/// Read the `sefstars.txt` catalogue from the configured ephemeris path.
+pub fn star_catalogue() -> Result<StarCatalogue, AstroError> {
+    Ephemeris::global().star_catalogue()
+}
+
+/// Find fixed stars conjunct the natal planets and angles.
+pub fn calculate_star_conjunctions(
+    birth: &BirthData,
+    options: &ChartOptions,
+    stars: &StarConjunctionOptions,
+) -> Result<Vec<StarConjunction>, AstroError> {
+    Ephemeris::global().star_conjunctions(birth, options, stars)
+}
+
+pub(crate) fn fixed_star_at(
+    ephe_flag: c_int,
+    name: &str,
+    jd: JulianDay,
+    observer: Option<&BirthData>,
+    options: &ChartOptions,
+) -> Result<FixedStarPosition, AstroError> {
+    let iflag = ephe_flag | ffi::SEFLG_SPEED | options.apply(observer)?;
+    star_position(name, jd, iflag, options.strict_ephemeris)
+}
+
+/// Like `fixed_star_at`, once `options.apply` has set up the library and `iflag`.
+fn star_position(
+    name: &str,
+    jd: JulianDay,
+    iflag: c_int,
+    strict_ephemeris: bool,
+) -> Result<FixedStarPosition, AstroError> {
+    let (mut star, calc) = match star_calc(name, jd.ut(), iflag) {
+        // Nomenclature lookups take a leading comma, e.g. ",alVir".
+        Err(AstroError::UnknownBody(_)) if !name.contains(',') => {
+            star_calc(&format!(",{}", name), jd.ut(), iflag)?
+        }
+        result => result?,
+    };
+    let source = checked_source(iflag, &calc, strict_ephemeris)?;
+
+    let mut magnitude = 0.0;
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    let rc = unsafe { ffi::swe_fixstar2_mag(star.as_mut_ptr(), &mut magnitude, serr.as_mut_ptr()) };
+    if rc < 0 {
+        return Err(ephemeris_error(&serr));
+    }
+
+    // The resolved "Name,nomenclature" written back by the C library.
+    let full_name = error_string(&star);
+    let (name, designation) = full_name.split_once(',').unwrap_or((&full_name, ""));
+    let longitude = calc.xx[0].rem_euclid(360.0);
+    Ok(FixedStarPosition {
+        name: name.trim().to_string(),
+        designation: designation.trim().to_string(),
+        longitude,
+        latitude: calc.xx[1],
+        distance: calc.xx[2],
+        longitude_speed: calc.xx[3],
+        magnitude,
+        source,
+        sign: Sign::from_longitude(longitude),
+        sign_degree: longitude % 30.0,
+    })
+}
+
+// Both C functions overwrite the star argument with "name,nomenclature", so leave room
+// for both parts and the terminating null.
+const STAR_BUF: usize = 2 * ffi::SE_MAX_STNAME + 1;
+
+fn star_calc(
+    name: &str,
+    tjd_ut: f64,
+    iflag: c_int,
+) -> Result<([c_char; STAR_BUF], Calc), AstroError> {
+    let c_name = CString::new(name)
+        .map_err(|_| AstroError::InvalidInput("star name contains null byte".into()))?;
+    let bytes = c_name.as_bytes_with_nul();
+    if bytes.len() > ffi::SE_MAX_STNAME {
+        return Err(AstroError::InvalidInput(format!(
+            "star name too long: {}",
+            name
+        )));
+    }
+    let mut star = [0 as c_char; STAR_BUF];
+    for (dst, &src) in star.iter_mut().zip(bytes) {
+        *dst = src as c_char;
+    }
+    let mut xx = [0f64; 6];
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    let rc = unsafe {
+        ffi::swe_fixstar2_ut(
+            star.as_mut_ptr(),
+            tjd_ut,
+            iflag,
+            xx.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    if rc < 0 {
+        return Err(ephemeris_error(&serr));
+    }
+    let warning = (serr[0] != 0).then(|| error_string(&serr));
+    Ok((
+        star,
+        Calc {
+            xx,
+            retflag: rc,
+            warning,
+        },
+    ))
+}
+
+/// A natal point checked against the fixed stars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartPoint {
+    Body(Body),
+    Ascendant,
+    Midheaven,
+    Descendant,
+    ImumCoeli,
+}
+
+/// How ecliptic latitude enters the conjunction test.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StarLatitude {
+    /// Compare longitudes only: the traditional zodiacal conjunction.
+    #[default]
+    Ignore,
+    /// Compare longitudes, skipping stars whose latitude differs from the point's by more
+    /// than this many degrees.
+    MaxDifference(f64),
+    /// Measure the true angular separation on the sky instead of the longitude difference.
+    Angular,
+}
+
+/// Options for `calculate_star_conjunctions`.
+#[derive(Debug, Clone)]
+pub struct StarConjunctionOptions {
+    /// Orb in degrees; with `scale_by_magnitude`, the orb of a first-magnitude star.
+    pub orb: f64,
+    /// Skip stars fainter than this visual magnitude; `None` checks the whole catalogue.
+    pub max_magnitude: Option<f64>,
+    /// Widen the orb for brighter stars and narrow it for fainter ones; see `orb_for`.
+    pub scale_by_magnitude: bool,
+    pub latitude: StarLatitude,
+    /// Check the Ascendant, Midheaven, Descendant, and IC as well as the bodies.
+    pub include_angles: bool,
+}
+
+impl Default for StarConjunctionOptions {
+    fn default() -> Self {
+        StarConjunctionOptions {
+            orb: 1.0,
+            max_magnitude: Some(3.0),
+            scale_by_magnitude: false,
+            latitude: StarLatitude::Ignore,
+            include_angles: true,
+        }
+    }
+}
+
+impl StarConjunctionOptions {
+    /// Orb allowed for a star of the given magnitude. When scaled, each magnitude fainter
+    /// than 1 takes a quarter off `orb` and each brighter one adds a quarter, within
+    /// 0.25 to 2 times `orb`.
+    pub fn orb_for(&self, magnitude: f64) -> f64 {
+        if !self.scale_by_magnitude {
+            return self.orb;
+        }
+        self.orb * (1.0 - (magnitude - 1.0) / 4.0).clamp(0.25, 2.0)
+    }
+
+    /// Separation in degrees between a point and a star, or `None` when the latitude
+    /// test rules the star out.
+    fn separation(&self, point: (f64, f64), star: &FixedStarPosition) -> Option<f64> {
+        let longitude = {
+            let d = (point.0 - star.longitude).rem_euclid(360.0);
+            d.min(360.0 - d)
+        };
+        match self.latitude {
+            StarLatitude::Ignore => Some(longitude),
+            StarLatitude::MaxDifference(limit) => {
+                ((point.1 - star.latitude).abs() <= limit).then_some(longitude)
+            }
+            StarLatitude::Angular => {
+                let (b1, b2) = (point.1.to_radians(), star.latitude.to_radians());
+                let cos = b1.sin() * b2.sin() + b1.cos() * b2.cos() * longitude.to_radians().cos();
+                Some(cos.clamp(-1.0, 1.0).acos().to_degrees())
+            }
+        }
+    }
+}
+
+/// A fixed star within orb of a natal point.
+#[derive(Debug, Clone)]
+pub struct StarConjunction {
+    pub point: ChartPoint,
+    pub point_longitude: f64, // ecliptic longitude of the natal point in degrees
+    pub star: FixedStarPosition,
+    pub separation: f64, // degrees, measured as set by `StarConjunctionOptions::latitude`
+    pub orb: f64,        // orb allowed for this star
+}
+
+/// Conjunctions between the chart points at `jd` and every catalogue star passing the
+/// magnitude limit, ordered by point and then by separation.
+pub(crate) fn star_conjunctions(
+    ephe_flag: c_int,
+    jd: JulianDay,
+    birth: &BirthData,
+    catalogue: &StarCatalogue,
+    options: &ChartOptions,
+    stars: &StarConjunctionOptions,
+) -> Result<Vec<StarConjunction>, AstroError> {
+    let chart = chart::full_chart_at(ephe_flag, jd, Some(birth), options)?;
+    let mut points: Vec<(ChartPoint, f64, f64)> = chart
+        .positions
+        .iter()
+        .map(|p| (ChartPoint::Body(p.body), p.longitude, p.latitude))
+        .collect();
+    if stars.include_angles {
+        // The angles are the same in every house system; Porphyry works at any latitude.
+        let angles = houses::houses(ephe_flag, jd, birth, HouseSystem::Porphyry, options)?.angles;
+        let (asc, mc) = (angles.ascendant.longitude, angles.mc.longitude);
+        points.extend([
+            (ChartPoint::Ascendant, asc, 0.0),
+            (ChartPoint::Midheaven, mc, 0.0),
+            (ChartPoint::Descendant, (asc + 180.0) % 360.0, 0.0),
+            (ChartPoint::ImumCoeli, (mc + 180.0) % 360.0, 0.0),
+        ]);
+    }
+
+    let iflag = ephe_flag | ffi::SEFLG_SPEED | options.apply(Some(birth))?;
+    // Some stars are listed more than once, under alternative names; stars without a
+    // nomenclature are looked up by name instead.
+    let mut seen = HashSet::new();
+    let positions = catalogue
+        .iter()
+        .filter(|s| stars.max_magnitude.is_none_or(|max| s.magnitude <= max))
+        .filter(|s| {
+            let key = if s.designation.is_empty() {
+                &s.name
+            } else {
+                &s.designation
+            };
+            seen.insert(key.as_str())
+        })
+        .map(|s| {
+            let lookup = if s.designation.is_empty() {
+                s.name.clone()
+            } else {
+                format!(",{}", s.designation)
+            };
+            star_position(&lookup, jd, iflag, options.strict_ephemeris)
+        })
+        .collect::<Result<Vec<_>, AstroError>>()?;
+
+    let mut conjunctions = Vec::new();
+    for &(point, longitude, latitude) in &points {
+        let start = conjunctions.len();
+        for star in &positions {
+            let orb = stars.orb_for(star.magnitude);
+            match stars.separation((longitude, latitude), star) {
+                Some(separation) if separation <= orb => conjunctions.push(StarConjunction {
+                    point,
+                    point_longitude: longitude,
+                    star: star.clone(),
+                    separation,
+                    orb,
+                }),
+                _ => {}
+            }
+        }
+        conjunctions[start..].sort_by(|a, b| a.separation.total_cmp(&b.separation));
+    }
+    Ok(conjunctions)
+}
+
+/// One star record from `sefstars.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarRecord {
+    pub name: String,          // traditional name; some stars appear under several names
+    pub designation: String,   // nomenclature, e.g. "alTau"
+    pub equinox: String,       // "ICRS", "2000", or "1950"
+    pub right_ascension: f64,  // degrees at the catalogue equinox
+    pub declination: f64,      // degrees at the catalogue equinox
+    pub ra_proper_motion: f64, // 0.001"/year, times cos(declination)
+    pub dec_proper_motion: f64, // 0.001"/year
+    pub radial_velocity: f64,  // km/s
+    pub parallax: f64,         // 0.001"
+    pub magnitude: f64,        // visual magnitude
+}
+
+/// The fixed stars listed in a `sefstars.txt` catalogue, in file order.
+#[derive(Debug, Clone)]
+pub struct StarCatalogue {
+    records: Vec<StarRecord>,
+}
+
+impl StarCatalogue {
+    /// Read and parse a catalogue file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AstroError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| AstroError::MissingEphemerisFile {
+            file: STAR_FILE.to_string(),
+            message: format!("cannot read {}: {}", path.display(), err),
+        })?;
+        StarCatalogue::parse(&text)
+    }
+
+    /// Parse catalogue text; `#` lines and blank lines are skipped.
+    pub fn parse(text: &str) -> Result<Self, AstroError> {
+        let records = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                parse_record(line).ok_or_else(|| {
+                    AstroError::InvalidInput(format!(
+                        "malformed star record on line {}: {}",
+                        index + 1,
+                        line
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(StarCatalogue { records })
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, StarRecord> {
+        self.records.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Stars brighter than (numerically below) `magnitude`.
+    pub fn brighter_than(&self, magnitude: f64) -> impl Iterator<Item = &StarRecord> {
+        self.records.iter().filter(move |s| s.magnitude < magnitude)
+    }
+}
+
+impl<'a> IntoIterator for &'a StarCatalogue {
+    type Item = &'a StarRecord;
+    type IntoIter = std::slice::Iter<'a, StarRecord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+impl IntoIterator for StarCatalogue {
+    type Item = StarRecord;
+    type IntoIter = std::vec::IntoIter<StarRecord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+fn parse_record(line: &str) -> Option<StarRecord> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() < 14 {
+        return None;
+    }
+    let num = |i: usize| fields[i].parse::<f64>().ok();
+    let right_ascension = (num(3)? + num(4)? / 60.0 + num(5)? / 3600.0) * 15.0;
+    // The sign sits on the degrees field, which may be "-00".
+    let declination = num(6)?.abs() + num(7)? / 60.0 + num(8)? / 3600.0;
+    let declination = if fields[6].starts_with('-') {
+        -declination
+    } else {
+        declination
+    };
+    Some(StarRecord {
+        name: fields[0].to_string(),
+        designation: fields[1].to_string(),
+        equinox: fields[2].to_string(),
+        right_ascension,
+        declination,
+        ra_proper_motion: num(9)?,
+        dec_proper_motion: num(10)?,
+        radial_velocity: num(11)?,
+        parallax: num(12)?,
+        magnitude: num(13)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{set_ephe_path, BirthData, Calendar};
+
+    #[test]
+    fn calculates_fixed_stars_and_reads_catalogue() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping calculates_fixed_stars_and_reads_catalogue: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let jd = JulianDay::from_utc(&BirthData {
+            year: 2000,
+            month: 1,
+            day: 1,
+            hour: 12,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        })
+        .unwrap();
+
+        // Regulus sat at the very end of Leo around 2000.
+        let regulus = fixed_star("Regulus", jd).unwrap();
+        assert_eq!(
+            (regulus.name.as_str(), regulus.designation.as_str()),
+            ("Regulus", "alLeo")
+        );
+        assert_eq!(regulus.sign, Sign::Leo);
+        assert!((29.5..30.0).contains(&regulus.sign_degree));
+        assert!((regulus.magnitude - 1.4).abs() < 0.01);
+
+        // Nomenclature works with or without the leading comma.
+        let aldebaran = fixed_star("alTau", jd).unwrap();
+        assert_eq!(aldebaran.name, "Aldebaran");
+        assert_eq!(aldebaran.sign, Sign::Gemini);
+        assert!((aldebaran.magnitude - 0.86).abs() < 0.01);
+        assert_eq!(
+            fixed_star(",alTau", jd).unwrap().longitude,
+            aldebaran.longitude
+        );
+        assert!(matches!(
+            fixed_star("Vulcan", jd),
+            Err(AstroError::UnknownBody(_))
+        ));
+
+        let catalogue = star_catalogue().unwrap();
+        assert!(catalogue.len() > 1000);
+        let sirius = catalogue.iter().find(|s| s.designation == "alCMa").unwrap();
+        assert!((sirius.declination + 16.716).abs() < 1e-3);
+        assert!((sirius.right_ascension - 101.287).abs() < 1e-3);
+        let bright: Vec<_> = catalogue.brighter_than(1.0).collect();
+        assert!(bright.iter().any(|s| s.name == "Sirius"));
+        assert!(bright.iter().all(|s| s.magnitude < 1.0));
+        assert!(!bright.iter().any(|s| s.name == "Regulus"));
+
+        assert!(StarCatalogue::parse("# comment\nBad,alBad,ICRS,04\n").is_err());
+        assert!(matches!(
+            Ephemeris::new("does/not/exist").unwrap().star_catalogue(),
+            Err(AstroError::MissingEphemerisFile { .. })
+        ));
+    }
+
+    #[test]
+    fn finds_star_conjunctions() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping finds_star_conjunctions: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let ephemeris = Ephemeris::new(ephe_path).unwrap();
+        // The Sun passed Regulus around 22 August 2000.
+        let birth = BirthData::builder()
+            .date(2000, 8, 22)
+            .time(12, 0, 0.0)
+            .location(51.5, -0.1)
+            .build()
+            .unwrap();
+        let options = ChartOptions::default();
+        let find = |stars: &StarConjunctionOptions| {
+            ephemeris
+                .star_conjunctions(&birth, &options, stars)
+                .unwrap()
+                .into_iter()
+                .find(|c| c.point == ChartPoint::Body(Body::Sun) && c.star.name == "Regulus")
+        };
+
+        let defaults = StarConjunctionOptions::default();
+        let sun = find(&defaults).expect("Sun conjunct Regulus");
+        assert!(sun.separation <= 1.0);
+        let all = ephemeris
+            .star_conjunctions(&birth, &options, &defaults)
+            .unwrap();
+        assert!(all
+            .iter()
+            .all(|c| c.star.magnitude <= 3.0 && c.separation <= c.orb));
+
+        // Regulus lies about 0.46 degrees north of the ecliptic.
+        let strict_latitude = StarConjunctionOptions {
+            latitude: StarLatitude::MaxDifference(0.3),
+            ..StarConjunctionOptions::default()
+        };
+        assert!(find(&strict_latitude).is_none());
+        let angular = StarConjunctionOptions {
+            latitude: StarLatitude::Angular,
+            ..StarConjunctionOptions::default()
+        };
+        assert!(find(&angular).unwrap().separation > sun.separation);
+
+        // A record without a nomenclature is looked up by name instead of being dropped.
+        let unnamed = StarCatalogue::parse(
+            "Regulus,,ICRS,10,08,22.31099,+11,58,01.9516,-248.73,5.59,5.9,41.13,1.4, 12, 2149\n",
+        )
+        .unwrap();
+        let jd = ephemeris.julian_day(&birth).unwrap();
+        let by_name = {
+            let session = ephemeris.session().unwrap();
+            star_conjunctions(session.ephe_flag, jd, &birth, &unnamed, &options, &defaults).unwrap()
+        };
+        let regulus = by_name
+            .iter()
+            .find(|c| c.point == ChartPoint::Body(Body::Sun))
+            .expect("Sun conjunct Regulus by name");
+        assert_eq!(regulus.star.name, "Regulus");
+        assert_eq!(regulus.separation, sun.separation);
+
+        let scaled = StarConjunctionOptions {
+            orb: 2.0,
+            scale_by_magnitude: true,
+            ..StarConjunctionOptions::default()
+        };
+        assert_eq!(scaled.orb_for(1.0), 2.0);
+        assert_eq!(scaled.orb_for(3.0), 1.0);
+        assert!((scaled.orb_for(-1.46) - 3.23).abs() < 1e-9);
+        assert_eq!(scaled.orb_for(9.0), 0.5);
+    }
+}
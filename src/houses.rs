@@ -0,0 +1,478 @@
+use libc::{c_char, c_int};
+
+use crate::error::error_string;
+use crate::ffi;
+use crate::{AstroError, BirthData, ChartOptions, Ephemeris, JulianDay, Sign};
+
+/// House systems supported by Swiss Ephemeris, keyed by their one-letter code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HouseSystem {
+    Placidus,
+    Koch,
+    Porphyry,
+    Regiomontanus,
+    Campanus,
+    Equal,
+    EqualMc,
+    EqualAries,
+    Vehlow,
+    WholeSign,
+    Alcabitius,
+    Morinus,
+    Topocentric,
+    Meridian,
+    Sripati,
+    Horizon,
+    CarterPoliEquatorial,
+    KrusinskiPisaGoelzer,
+    PullenSd,
+    PullenSr,
+    Sunshine,
+    SunshineMakransky,
+    SavardA,
+    Apc,
+    /// Gauquelin sectors; returns 36 cusps instead of 12.
+    Gauquelin,
+}
+
+impl HouseSystem {
+    pub const ALL: [HouseSystem; 25] = [
+        HouseSystem::Placidus,
+        HouseSystem::Koch,
+        HouseSystem::Porphyry,
+        HouseSystem::Regiomontanus,
+        HouseSystem::Campanus,
+        HouseSystem::Equal,
+        HouseSystem::EqualMc,
+        HouseSystem::EqualAries,
+        HouseSystem::Vehlow,
+        HouseSystem::WholeSign,
+        HouseSystem::Alcabitius,
+        HouseSystem::Morinus,
+        HouseSystem::Topocentric,
+        HouseSystem::Meridian,
+        HouseSystem::Sripati,
+        HouseSystem::Horizon,
+        HouseSystem::CarterPoliEquatorial,
+        HouseSystem::KrusinskiPisaGoelzer,
+        HouseSystem::PullenSd,
+        HouseSystem::PullenSr,
+        HouseSystem::Sunshine,
+        HouseSystem::SunshineMakransky,
+        HouseSystem::SavardA,
+        HouseSystem::Apc,
+        HouseSystem::Gauquelin,
+    ];
+
+    /// The Swiss Ephemeris `hsys` letter, e.g. `'P'` for Placidus.
+    pub fn code(self) -> char {
+        match self {
+            HouseSystem::Placidus => 'P',
+            HouseSystem::Koch => 'K',
+            HouseSystem::Porphyry => 'O',
+            HouseSystem::Regiomontanus => 'R',
+            HouseSystem::Campanus => 'C',
+            HouseSystem::Equal => 'E',
+            HouseSystem::EqualMc => 'D',
+            HouseSystem::EqualAries => 'N',
+            HouseSystem::Vehlow => 'V',
+            HouseSystem::WholeSign => 'W',
+            HouseSystem::Alcabitius => 'B',
+            HouseSystem::Morinus => 'M',
+            HouseSystem::Topocentric => 'T',
+            HouseSystem::Meridian => 'X',
+            HouseSystem::Sripati => 'S',
+            HouseSystem::Horizon => 'H',
+            HouseSystem::CarterPoliEquatorial => 'F',
+            HouseSystem::KrusinskiPisaGoelzer => 'U',
+            HouseSystem::PullenSd => 'L',
+            HouseSystem::PullenSr => 'Q',
+            HouseSystem::Sunshine => 'I',
+            HouseSystem::SunshineMakransky => 'i',
+            HouseSystem::SavardA => 'J',
+            HouseSystem::Apc => 'Y',
+            HouseSystem::Gauquelin => 'G',
+        }
+    }
+
+    /// Look up a house system by its Swiss Ephemeris letter. `'A'` is accepted as Equal.
+    pub fn from_code(code: char) -> Option<HouseSystem> {
+        if code == 'A' {
+            return Some(HouseSystem::Equal);
+        }
+        HouseSystem::ALL.into_iter().find(|h| h.code() == code)
+    }
+
+    /// Number of cusps returned for this system: 36 for Gauquelin, 12 otherwise.
+    pub fn cusp_count(self) -> usize {
+        match self {
+            HouseSystem::Gauquelin => 36,
+            _ => 12,
+        }
+    }
+}
+
+/// An ecliptic angle or sensitive point such as the MC or Vertex.
+#[derive(Debug, Clone)]
+pub struct AnglePoint {
+    pub longitude: f64, // ecliptic longitude in degrees, 0-360
+    pub speed: f64,     // degrees/day
+    pub sign: Sign,
+}
+
+impl AnglePoint {
+    fn new(longitude: f64, speed: f64) -> Self {
+        let longitude = longitude.rem_euclid(360.0);
+        AnglePoint {
+            longitude,
+            speed,
+            sign: Sign::from_longitude(longitude),
+        }
+    }
+}
+
+/// The angles Swiss Ephemeris reports alongside house cusps (its `ascmc` array).
+#[derive(Debug, Clone)]
+pub struct Angles {
+    pub ascendant: AnglePoint,
+    pub mc: AnglePoint,
+    /// Right ascension of the MC (sidereal time in degrees); not an ecliptic point.
+    pub armc: f64,
+    pub vertex: AnglePoint,
+    pub equatorial_ascendant: AnglePoint,
+    /// Co-ascendant after W. Koch.
+    pub co_ascendant_koch: AnglePoint,
+    /// Co-ascendant after M. Munkasey.
+    pub co_ascendant_munkasey: AnglePoint,
+    /// Polar ascendant after M. Munkasey.
+    pub polar_ascendant: AnglePoint,
+}
+
+impl Angles {
+    fn from_ascmc(ascmc: &[f64], speeds: &[f64]) -> Self {
+        let point = |i: usize| AnglePoint::new(ascmc[i], speeds[i]);
+        Angles {
+            ascendant: point(ffi::SE_ASC),
+            mc: point(ffi::SE_MC),
+            armc: ascmc[ffi::SE_ARMC],
+            vertex: point(ffi::SE_VERTEX),
+            equatorial_ascendant: point(ffi::SE_EQUASC),
+            co_ascendant_koch: point(ffi::SE_COASC1),
+            co_ascendant_munkasey: point(ffi::SE_COASC2),
+            polar_ascendant: point(ffi::SE_POLASC),
+        }
+    }
+}
+
+/// What to do when the requested house system cannot be computed, e.g. Placidus or Koch
+/// inside the polar circles (above roughly ±66°).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PolarFallback {
+    /// Return an error instead of substitute cusps.
+    Error,
+    /// Recompute the houses with the given system.
+    Fallback(HouseSystem),
+    /// Keep the Porphyry cusps Swiss Ephemeris substitutes and report it in `effective_system`.
+    #[default]
+    Report,
+}
+
+/// House cusps and angle points for one chart.
+#[derive(Debug, Clone)]
+pub struct HouseResult {
+    /// The house system that was requested.
+    pub system: HouseSystem,
+    /// The house system the cusps were actually computed with.
+    pub effective_system: HouseSystem,
+    /// Why `effective_system` differs from `system`, as reported by Swiss Ephemeris.
+    pub fallback_reason: Option<String>,
+    pub cusps: Vec<f64>,       // cusps[0] is the 1st house cusp, in degrees
+    pub cusp_speeds: Vec<f64>, // degrees/day
+    pub angles: Angles,
+}
+
+impl HouseResult {
+    /// True when the cusps come from a different system than the one requested.
+    pub fn is_fallback(&self) -> bool {
+        self.effective_system != self.system
+    }
+
+    /// Cusp of the given house, numbered from 1.
+    pub fn cusp(&self, house: usize) -> Option<f64> {
+        house
+            .checked_sub(1)
+            .and_then(|i| self.cusps.get(i).copied())
+    }
+}
+
+/// Calculate house cusps for the birth time and location in the given system.
+///
+/// Systems that fail inside the polar circles fall back to Porphyry; see `PolarFallback::Report`.
+pub fn calculate_houses(birth: &BirthData, system: HouseSystem) -> Result<HouseResult, AstroError> {
+    calculate_houses_with(birth, system, &ChartOptions::default())
+}
+
+/// Like `calculate_houses`, honoring the zodiac and polar fallback policy in `options`.
+pub fn calculate_houses_with(
+    birth: &BirthData,
+    system: HouseSystem,
+    options: &ChartOptions,
+) -> Result<HouseResult, AstroError> {
+    Ephemeris::global().houses(birth, system, options)
+}
+
+pub(crate) fn houses(
+    ephe_flag: c_int,
+    jd: JulianDay,
+    birth: &BirthData,
+    system: HouseSystem,
+    options: &ChartOptions,
+) -> Result<HouseResult, AstroError> {
+    let iflag = ephe_flag | options.apply(Some(birth))?;
+    houses_with_fallback(
+        jd.ut(),
+        birth.lat,
+        birth.lon,
+        system,
+        iflag,
+        options.polar_fallback,
+    )
+}
+
+pub(crate) fn houses_with_fallback(
+    tjd_ut: f64,
+    lat: f64,
+    lon: f64,
+    system: HouseSystem,
+    iflag: c_int,
+    fallback: PolarFallback,
+) -> Result<HouseResult, AstroError> {
+    let houses = house_cusps(tjd_ut, lat, lon, system, iflag)?;
+    if !houses.is_fallback() {
+        return Ok(houses);
+    }
+    let reason = houses.fallback_reason.clone().unwrap_or_default();
+    match fallback {
+        PolarFallback::Report => Ok(houses),
+        PolarFallback::Error => Err(AstroError::HouseSystemFailure {
+            system,
+            reason: format!("latitude {}: {}", lat, reason),
+        }),
+        PolarFallback::Fallback(other) => {
+            let mut houses = house_cusps(tjd_ut, lat, lon, other, iflag)?;
+            if houses.is_fallback() {
+                return Err(AstroError::HouseSystemFailure {
+                    system: other,
+                    reason: format!(
+                        "fallback at latitude {}: {}",
+                        lat,
+                        houses.fallback_reason.unwrap_or_default()
+                    ),
+                });
+            }
+            houses.system = system;
+            houses.fallback_reason = Some(reason);
+            Ok(houses)
+        }
+    }
+}
+
+/// Raw `swe_houses_ex2` call. A negative return code means Swiss Ephemeris could not
+/// compute `system` and filled in 12 Porphyry cusps instead, explaining why in `serr`.
+pub(crate) fn house_cusps(
+    tjd_ut: f64,
+    lat: f64,
+    lon: f64,
+    system: HouseSystem,
+    iflag: c_int,
+) -> Result<HouseResult, AstroError> {
+    // Swiss Ephemeris uses 1-based cusps; Gauquelin needs 37 slots.
+    let mut cusps = [0f64; 37];
+    let mut cusp_speeds = [0f64; 37];
+    let mut ascmc = [0f64; 10];
+    let mut ascmc_speeds = [0f64; 10];
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    let rc = unsafe {
+        ffi::swe_houses_ex2(
+            tjd_ut,
+            iflag,
+            lat,
+            lon,
+            system.code() as c_int,
+            cusps.as_mut_ptr(),
+            ascmc.as_mut_ptr(),
+            cusp_speeds.as_mut_ptr(),
+            ascmc_speeds.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    let (effective_system, fallback_reason) = if rc < 0 {
+        (HouseSystem::Porphyry, Some(error_string(&serr)))
+    } else {
+        (system, None)
+    };
+
+    let n = effective_system.cusp_count();
+    Ok(HouseResult {
+        system,
+        effective_system,
+        fallback_reason,
+        cusps: cusps[1..=n].to_vec(),
+        cusp_speeds: cusp_speeds[1..=n].to_vec(),
+        angles: Angles::from_ascmc(&ascmc, &ascmc_speeds),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{set_ephe_path, Ayanamsa, Calendar, EphemerisBackend, Zodiac};
+    use std::path::Path;
+
+    #[test]
+    fn calculates_houses_for_each_system() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping calculates_houses_for_each_system: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 1990,
+            month: 7,
+            day: 15,
+            hour: 10,
+            minute: 30,
+            second: 0.0,
+            lat: 40.7128,
+            lon: -74.0060,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+
+        for system in HouseSystem::ALL {
+            let houses = calculate_houses(&birth, system).expect("houses should compute");
+            assert_eq!(houses.effective_system, system);
+            assert_eq!(houses.cusps.len(), system.cusp_count());
+            assert_eq!(houses.cusp_speeds.len(), system.cusp_count());
+            assert_eq!(HouseSystem::from_code(system.code()), Some(system));
+        }
+
+        let whole = calculate_houses(&birth, HouseSystem::WholeSign).unwrap();
+        for cusp in &whole.cusps {
+            assert!(cusp % 30.0 < 1e-9);
+        }
+        let placidus = calculate_houses(&birth, HouseSystem::Placidus).unwrap();
+        let angles = &placidus.angles;
+        assert!((placidus.cusp(1).unwrap() - angles.ascendant.longitude).abs() < 1e-9);
+        assert!((placidus.cusp(10).unwrap() - angles.mc.longitude).abs() < 1e-9);
+        // The Vertex lies in the western half of the chart, opposite the anti-vertex.
+        let from_asc = (angles.vertex.longitude - angles.ascendant.longitude).rem_euclid(360.0);
+        assert!(from_asc > 90.0 && from_asc < 270.0);
+        assert!((0.0..360.0).contains(&angles.armc));
+    }
+
+    #[test]
+    fn applies_polar_fallback_policy() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping applies_polar_fallback_policy: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        // Tromsø, well inside the Arctic Circle.
+        let birth = BirthData {
+            year: 1990,
+            month: 12,
+            day: 21,
+            hour: 12,
+            minute: 0,
+            second: 0.0,
+            lat: 69.6492,
+            lon: 18.9553,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+
+        let reported = calculate_houses(&birth, HouseSystem::Placidus).unwrap();
+        assert_eq!(reported.system, HouseSystem::Placidus);
+        assert_eq!(reported.effective_system, HouseSystem::Porphyry);
+        assert!(reported.fallback_reason.is_some());
+        assert_eq!(reported.cusps.len(), 12);
+
+        let strict = ChartOptions {
+            polar_fallback: PolarFallback::Error,
+            ..ChartOptions::default()
+        };
+        let err = calculate_houses_with(&birth, HouseSystem::Koch, &strict).unwrap_err();
+        assert!(matches!(
+            err,
+            AstroError::HouseSystemFailure {
+                system: HouseSystem::Koch,
+                ..
+            }
+        ));
+
+        let options = ChartOptions {
+            polar_fallback: PolarFallback::Fallback(HouseSystem::WholeSign),
+            ..ChartOptions::default()
+        };
+        let whole = calculate_houses_with(&birth, HouseSystem::Placidus, &options).unwrap();
+        assert_eq!(whole.effective_system, HouseSystem::WholeSign);
+        assert!(whole.is_fallback());
+        assert!(whole.cusps.iter().all(|c| c % 30.0 < 1e-9));
+    }
+
+    #[test]
+    fn houses_use_the_context_backend() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping houses_use_the_context_backend: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let birth = BirthData {
+            year: 1990,
+            month: 7,
+            day: 15,
+            hour: 10,
+            minute: 30,
+            second: 0.0,
+            lat: 40.7128,
+            lon: -74.0060,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        // A true-star ayanamsa follows Spica, whose position depends on the ephemeris.
+        let options = ChartOptions {
+            zodiac: Zodiac::Sidereal(Ayanamsa::TrueCitra),
+            ..ChartOptions::default()
+        };
+        let swiss = Ephemeris::new(ephe_path).unwrap();
+        let moshier = swiss.clone().with_backend(EphemerisBackend::Moshier);
+        let from_swiss = swiss
+            .houses(&birth, HouseSystem::Placidus, &options)
+            .unwrap();
+        let from_moshier = moshier
+            .houses(&birth, HouseSystem::Placidus, &options)
+            .unwrap();
+
+        let direct = {
+            let session = moshier.session().unwrap();
+            assert_eq!(session.ephe_flag, ffi::SEFLG_MOSEPH);
+            let jd = session.julian_day(&birth).unwrap();
+            let iflag = ffi::SEFLG_MOSEPH | options.apply(Some(&birth)).unwrap();
+            house_cusps(jd.ut(), birth.lat, birth.lon, HouseSystem::Placidus, iflag).unwrap()
+        };
+        assert_eq!(direct.cusps, from_moshier.cusps);
+        assert_ne!(from_swiss.cusps, from_moshier.cusps);
+    }
+}
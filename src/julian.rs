@@ -0,0 +1,385 @@
+use libc::{c_char, c_double, c_int};
+
+use crate::error::ephemeris_error;
+use crate::{ffi, AstroError, BirthData, Calendar, Ephemeris, LeapSeconds};
+
+/// A moment as Julian day numbers in Universal Time (UT1) and Terrestrial Time (TT).
+///
+/// Computing it once and passing it to `Ephemeris::full_chart_at` or `Ephemeris::body_at`
+/// avoids repeating the UTC conversion for every calculation at the same moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JulianDay {
+    ut: f64,
+    tt: f64,
+}
+
+/// A UTC calendar date and time, as returned by `JulianDay::to_utc`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UtcDateTime {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    pub hour: i32,
+    pub minute: i32,
+    pub second: f64, // may reach 60.x during a leap second
+}
+
+impl JulianDay {
+    /// Julian day of the birth moment, which is read as UTC (UT1 before 1972).
+    pub fn from_utc(birth: &BirthData) -> Result<JulianDay, AstroError> {
+        Ephemeris::global().julian_day(birth)
+    }
+
+    /// Julian day from a UT1 Julian day number, adding Delta T for TT.
+    /// Use `Ephemeris::julian_day_from_ut` for a context's Delta T settings.
+    pub fn from_ut(ut: f64) -> Result<JulianDay, AstroError> {
+        Ephemeris::global().julian_day_from_ut(ut)
+    }
+
+    /// Julian day from a TT Julian day number, subtracting Delta T for UT1.
+    /// Use `Ephemeris::julian_day_from_tt` for a context's Delta T settings.
+    pub fn from_tt(tt: f64) -> Result<JulianDay, AstroError> {
+        Ephemeris::global().julian_day_from_tt(tt)
+    }
+
+    /// Julian day number in Universal Time (UT1), as used by `swe_calc_ut`.
+    pub fn ut(self) -> f64 {
+        self.ut
+    }
+
+    /// Julian day number in Terrestrial Time, as used by `swe_calc`.
+    pub fn tt(self) -> f64 {
+        self.tt
+    }
+
+    /// Delta T = TT - UT in days.
+    pub fn delta_t(self) -> f64 {
+        self.tt - self.ut
+    }
+
+    /// Delta T = TT - UT in seconds.
+    pub fn delta_t_seconds(self) -> f64 {
+        self.delta_t() * 86_400.0
+    }
+
+    /// UTC date and time of this moment, counting leap seconds since 1972. Use
+    /// `Ephemeris::to_utc` for a context's leap-second table and Delta T settings.
+    pub fn to_utc(self, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        Ephemeris::global().to_utc(self, calendar)
+    }
+
+    /// UTC date and time derived from the UT1 value instead of TT. The two agree to within
+    /// Delta T's modelling error, which matters only far from the present.
+    pub fn ut_to_utc(self, calendar: Calendar) -> Result<UtcDateTime, AstroError> {
+        Ephemeris::global().ut_to_utc(self, calendar)
+    }
+}
+
+/// Julian day from UT1. Callers hold an ephemeris session.
+pub(crate) fn from_ut(ut: f64, ephe_flag: c_int) -> Result<JulianDay, AstroError> {
+    let tt = ut + delta_t(ut, ephe_flag)?;
+    Ok(JulianDay { ut, tt })
+}
+
+/// Julian day from TT. Callers hold an ephemeris session.
+pub(crate) fn from_tt(tt: f64, ephe_flag: c_int) -> Result<JulianDay, AstroError> {
+    // Delta T is a function of UT; two iterations converge far below a microsecond.
+    let mut ut = tt - delta_t(tt, ephe_flag)?;
+    ut = tt - delta_t(ut, ephe_flag)?;
+    Ok(JulianDay { ut, tt })
+}
+
+/// UTC date and time of a moment. Callers hold an ephemeris session.
+///
+/// Inverts `julian_day`: with `leap_seconds`, UTC after 1972 comes from that table;
+/// otherwise `swe_jdet_to_utc` uses the table built into the C library.
+pub(crate) fn to_utc(
+    jd: JulianDay,
+    calendar: Calendar,
+    leap_seconds: Option<&LeapSeconds>,
+    ephe_flag: c_int,
+) -> Result<UtcDateTime, AstroError> {
+    let Some(table) = leap_seconds else {
+        return Ok(utc(
+            ffi::swe_jdet_to_utc,
+            jd.tt,
+            calendar.gregflag_at(jd.ut),
+        ));
+    };
+    let tai = jd.tt - TT_TAI / 86_400.0;
+    if tai < J1972 + TAI_UTC_1972 / 86_400.0 {
+        // Before 1972 the output is UT1.
+        return Ok(ut1_date_time(jd.ut, calendar));
+    }
+    // TAI at the start of the UTC day beginning at `midnight`.
+    let day_start = |midnight: f64| {
+        let (year, month, day, _) = Calendar::Gregorian.date(midnight);
+        let leaps = table.count_before(year, month, day);
+        midnight + (TAI_UTC_1972 + leaps as f64) / 86_400.0
+    };
+    let mut midnight = (tai - 0.5).floor() + 0.5;
+    while day_start(midnight) > tai {
+        midnight -= 1.0;
+    }
+    while day_start(midnight + 1.0) <= tai {
+        midnight += 1.0;
+    }
+    let tai_utc = (day_start(midnight) - midnight) * 86_400.0;
+
+    // Like `julian_day`: past the end of the table, read the moment as UT1.
+    if delta_t(midnight, ephe_flag)? * 86_400.0 - tai_utc - TT_TAI >= 1.0 {
+        return Ok(ut1_date_time(jd.ut, calendar));
+    }
+    let (year, month, day, _) = calendar.date(midnight);
+    let seconds = (tai - day_start(midnight)) * 86_400.0;
+    if seconds >= 86_400.0 {
+        // The leap second at the end of the day.
+        return Ok(UtcDateTime {
+            year,
+            month,
+            day,
+            hour: 23,
+            minute: 59,
+            second: seconds - 86_340.0,
+        });
+    }
+    Ok(date_time(year, month, day, seconds))
+}
+
+/// UTC date and time from the UT1 value. Callers hold an ephemeris session.
+///
+/// Without `leap_seconds` this is `swe_jdut1_to_utc`. The C function only knows its
+/// built-in table, so with a custom one TT is rebuilt from UT1 and the context's Delta T
+/// and converted by `to_utc`, as `swe_jdut1_to_utc` does internally.
+pub(crate) fn ut_to_utc(
+    jd: JulianDay,
+    calendar: Calendar,
+    leap_seconds: Option<&LeapSeconds>,
+    ephe_flag: c_int,
+) -> Result<UtcDateTime, AstroError> {
+    if leap_seconds.is_none() {
+        return Ok(utc(
+            ffi::swe_jdut1_to_utc,
+            jd.ut,
+            calendar.gregflag_at(jd.ut),
+        ));
+    }
+    let jd = from_ut(jd.ut, ephe_flag)?;
+    to_utc(jd, calendar, leap_seconds, ephe_flag)
+}
+
+fn ut1_date_time(ut: f64, calendar: Calendar) -> UtcDateTime {
+    let (year, month, day, hour) = calendar.date(ut);
+    date_time(year, month, day, hour * 3600.0)
+}
+
+fn date_time(year: i32, month: i32, day: i32, seconds: f64) -> UtcDateTime {
+    let hour = (seconds / 3600.0).floor();
+    let minute = ((seconds - hour * 3600.0) / 60.0).floor();
+    UtcDateTime {
+        year,
+        month,
+        day,
+        hour: hour as i32,
+        minute: minute as i32,
+        second: seconds - hour * 3600.0 - minute * 60.0,
+    }
+}
+
+type ToUtc = unsafe extern "C" fn(
+    c_double,
+    c_int,
+    *mut c_int,
+    *mut c_int,
+    *mut c_int,
+    *mut c_int,
+    *mut c_int,
+    *mut c_double,
+);
+
+fn utc(convert: ToUtc, tjd: f64, gregflag: c_int) -> UtcDateTime {
+    let (mut year, mut month, mut day, mut hour, mut minute) = (0, 0, 0, 0, 0);
+    let mut second = 0.0;
+    unsafe {
+        convert(
+            tjd,
+            gregflag,
+            &mut year,
+            &mut month,
+            &mut day,
+            &mut hour,
+            &mut minute,
+            &mut second,
+        );
+    }
+    UtcDateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+    }
+}
+
+/// Convert birth data read as UTC. Callers hold an ephemeris session.
+///
+/// With `leap_seconds`, UTC after 1972 is converted here using that table; otherwise
+/// `swe_utc_to_jd` uses the table built into the C library.
+pub(crate) fn julian_day(
+    birth: &BirthData,
+    leap_seconds: Option<&LeapSeconds>,
+    ephe_flag: c_int,
+) -> Result<JulianDay, AstroError> {
+    let Some(table) = leap_seconds else {
+        birth.validate()?;
+        return swe_julian_day(birth);
+    };
+    birth.validate_with(table)?;
+    let gregflag = birth
+        .calendar
+        .gregflag(birth.year, birth.month, birth.day)?;
+    let day = unsafe { ffi::swe_julday(birth.year, birth.month, birth.day, 0.0, gregflag) };
+    if day < J1972 {
+        // Before 1972 the input is UT1 and leap seconds do not apply.
+        return swe_julian_day(birth);
+    }
+    let time = (birth.hour as f64 + birth.minute as f64 / 60.0 + birth.second / 3600.0) / 24.0;
+    let (year, month, mday, _) = Calendar::Gregorian.date(day);
+    let tai_utc = TAI_UTC_1972 + table.count_before(year, month, mday) as f64;
+
+    // Like swe_utc_to_jd: if Delta T outruns the table (a future date it does not cover),
+    // read the input as UT1 rather than UTC.
+    if delta_t(day, ephe_flag)? * 86_400.0 - tai_utc - TT_TAI >= 1.0 {
+        let ut = day + time;
+        return Ok(JulianDay {
+            ut,
+            tt: ut + delta_t(ut, ephe_flag)?,
+        });
+    }
+    let tt = day + time + (tai_utc + TT_TAI) / 86_400.0;
+    let mut ut = tt - delta_t(tt - delta_t(tt, ephe_flag)?, ephe_flag)?;
+    ut = tt - delta_t(ut, ephe_flag)?;
+    Ok(JulianDay { ut, tt })
+}
+
+// 1972-01-01 00:00 UTC, when TAI - UTC became a whole number of seconds (10 s).
+const J1972: f64 = 2_441_317.5;
+const TAI_UTC_1972: f64 = 10.0;
+const TT_TAI: f64 = 32.184;
+
+fn swe_julian_day(birth: &BirthData) -> Result<JulianDay, AstroError> {
+    let mut dret = [0f64; 2];
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    let rc = unsafe {
+        ffi::swe_utc_to_jd(
+            birth.year as c_int,
+            birth.month as c_int,
+            birth.day as c_int,
+            birth.hour as c_int,
+            birth.minute as c_int,
+            birth.second,
+            birth
+                .calendar
+                .gregflag(birth.year, birth.month, birth.day)?,
+            dret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    if rc < 0 {
+        return Err(ephemeris_error(&serr));
+    }
+    // dret[0] = TT, dret[1] = UT1
+    Ok(JulianDay {
+        ut: dret[1],
+        tt: dret[0],
+    })
+}
+
+fn delta_t(tjd: f64, ephe_flag: c_int) -> Result<f64, AstroError> {
+    if !tjd.is_finite() {
+        return Err(AstroError::InvalidInput(format!(
+            "Julian day must be finite, got {}",
+            tjd
+        )));
+    }
+    // Any message only notes which ephemeris' tidal acceleration was used; the value is valid.
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    Ok(unsafe { ffi::swe_deltat_ex(tjd, ephe_flag, serr.as_mut_ptr()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{set_ephe_path, Body, CenterMode, ChartOptions};
+    use std::path::Path;
+
+    fn birth(day: i32, hour: i32, minute: i32, second: f64) -> BirthData {
+        BirthData {
+            year: 2016,
+            month: 12,
+            day,
+            hour,
+            minute,
+            second,
+            lat: 51.4769,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        }
+    }
+
+    #[test]
+    fn converts_between_utc_ut_and_tt() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping converts_between_utc_ut_and_tt: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+
+        // TT - UTC was 68.184 s at the end of 2016; UT1 stays within 0.9 s of UTC.
+        let jd = JulianDay::from_utc(&birth(31, 12, 0, 0.0)).unwrap();
+        assert!((jd.delta_t_seconds() - 68.184).abs() < 1.0);
+        let utc = jd.to_utc(Calendar::Gregorian).unwrap();
+        assert_eq!(
+            (utc.year, utc.month, utc.day, utc.hour, utc.minute),
+            (2016, 12, 31, 12, 0)
+        );
+        assert!(utc.second.abs() < 1e-3);
+        let via_ut = jd.ut_to_utc(Calendar::Gregorian).unwrap();
+        assert_eq!((via_ut.day, via_ut.hour), (31, 12));
+
+        // The leap second at the end of 2016 survives the round trip through TT.
+        let leap = JulianDay::from_utc(&birth(31, 23, 59, 60.5)).unwrap();
+        let utc = leap.to_utc(Calendar::Gregorian).unwrap();
+        assert_eq!((utc.day, utc.hour, utc.minute), (31, 23, 59));
+        assert!((utc.second - 60.5).abs() < 1e-3);
+
+        // Delta T from swe_deltat_ex agrees with the leap-second count to within a second.
+        let from_ut = JulianDay::from_ut(jd.ut()).unwrap();
+        assert!((from_ut.tt() - jd.tt()).abs() * 86_400.0 < 1.0);
+        let from_tt = JulianDay::from_tt(from_ut.tt()).unwrap();
+        assert!((from_tt.ut() - jd.ut()).abs() * 86_400.0 < 1e-3);
+
+        // A cached Julian day gives the same chart as the birth data it came from.
+        let ephemeris = Ephemeris::new(ephe_path).unwrap();
+        let options = ChartOptions::default();
+        let from_birth = ephemeris
+            .body(&birth(31, 12, 0, 0.0), Body::Moon, &options)
+            .unwrap();
+        let cached = ephemeris.body_at(jd, Body::Moon, &options).unwrap();
+        assert_eq!(cached.longitude, from_birth.longitude);
+        let topocentric = ChartOptions {
+            center: CenterMode::Topocentric,
+            ..ChartOptions::default()
+        };
+        assert!(matches!(
+            ephemeris.full_chart_at(jd, &topocentric),
+            Err(AstroError::InvalidInput(_))
+        ));
+    }
+}
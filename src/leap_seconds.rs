@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{AstroError, Calendar};
+
+/// UTC dates (YYYYMMDD) whose last minute had a leap second, 23:59:60, matching the
+/// table built into the Swiss Ephemeris.
+const BUILTIN_DATES: [i32; 27] = [
+    19720630, 19721231, 19731231, 19741231, 19751231, 19761231, 19771231, 19781231, 19791231,
+    19810630, 19820630, 19830630, 19850630, 19871231, 19891231, 19901231, 19920630, 19930630,
+    19940630, 19951231, 19970630, 19981231, 20051231, 20081231, 20120630, 20150630, 20161231,
+];
+
+/// Table of UTC leap seconds used to convert UTC to Terrestrial Time.
+///
+/// Each date (YYYYMMDD) is a day that ended with a 23:59:60 leap second. Install a newer
+/// table with `Ephemeris::with_leap_seconds` when a leap second is announced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeapSeconds {
+    dates: Vec<i32>,
+}
+
+impl Default for LeapSeconds {
+    fn default() -> Self {
+        LeapSeconds::builtin()
+    }
+}
+
+impl LeapSeconds {
+    /// Leap seconds known to the bundled Swiss Ephemeris, up to 2016-12-31.
+    pub fn builtin() -> Self {
+        LeapSeconds {
+            dates: BUILTIN_DATES.to_vec(),
+        }
+    }
+
+    /// Exactly the given leap-second dates, in any order.
+    pub fn from_dates(dates: &[i32]) -> Result<Self, AstroError> {
+        let mut table = LeapSeconds { dates: Vec::new() };
+        table.add(dates)?;
+        Ok(table)
+    }
+
+    /// The built-in table extended with dates in Swiss Ephemeris' `seleapsec.txt` format:
+    /// one YYYYMMDD date per line, `#` starting a comment line.
+    pub fn parse(text: &str) -> Result<Self, AstroError> {
+        let mut dates = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let date = line.parse::<i32>().map_err(|_| {
+                AstroError::InvalidInput(format!("invalid leap-second date '{}'", line))
+            })?;
+            dates.push(date);
+        }
+        let mut table = LeapSeconds::builtin();
+        table.add(&dates)?;
+        Ok(table)
+    }
+
+    /// Read a `seleapsec.txt`-style file; see `parse`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AstroError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| {
+            AstroError::InvalidInput(format!(
+                "cannot read leap-second file {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        LeapSeconds::parse(&text)
+    }
+
+    /// Leap-second dates as YYYYMMDD, ascending.
+    pub fn dates(&self) -> &[i32] {
+        &self.dates
+    }
+
+    /// True when the Gregorian date ended with a leap second.
+    pub fn is_leap_second_day(&self, year: i32, month: i32, day: i32) -> bool {
+        let date = date_key(year, month, day);
+        self.dates
+            .binary_search_by_key(&date, |&d| i64::from(d))
+            .is_ok()
+    }
+
+    /// Leap seconds inserted before the given Gregorian date.
+    pub(crate) fn count_before(&self, year: i32, month: i32, day: i32) -> usize {
+        let date = date_key(year, month, day);
+        self.dates.partition_point(|&d| i64::from(d) < date)
+    }
+
+    fn add(&mut self, dates: &[i32]) -> Result<(), AstroError> {
+        for &date in dates {
+            let (year, month, day) = (date / 10000, date / 100 % 100, date % 100);
+            if year < 1972
+                || Calendar::Gregorian
+                    .julian_day(year, month, day, 0.0)
+                    .is_err()
+            {
+                return Err(AstroError::InvalidInput(format!(
+                    "invalid leap-second date {}",
+                    date
+                )));
+            }
+            self.dates.push(date);
+        }
+        self.dates.sort_unstable();
+        self.dates.dedup();
+        Ok(())
+    }
+}
+
+/// YYYYMMDD key of a date, widened so that no year overflows it.
+fn date_key(year: i32, month: i32, day: i32) -> i64 {
+    i64::from(year) * 10000 + i64::from(month) * 100 + i64::from(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BirthData, Ephemeris};
+    use std::path::Path;
+
+    fn birth(year: i32, month: i32, day: i32, hour: i32, minute: i32, second: f64) -> BirthData {
+        BirthData {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        }
+    }
+
+    #[test]
+    fn parses_leap_second_tables() {
+        let table = LeapSeconds::parse("# added by IERS Bulletin C\n20261231\n\n").unwrap();
+        assert_eq!(
+            table.dates().len(),
+            LeapSeconds::builtin().dates().len() + 1
+        );
+        assert!(table.is_leap_second_day(2026, 12, 31));
+        assert!(table.is_leap_second_day(1972, 6, 30));
+        assert_eq!(table.count_before(2027, 1, 1), 28);
+        assert_eq!(table.count_before(300_000, 1, 1), 28);
+        assert_eq!(table.count_before(-300_000, 1, 1), 0);
+
+        let exact = LeapSeconds::from_dates(&[20161231, 19720630]).unwrap();
+        assert_eq!(exact.dates(), &[19720630, 20161231]);
+        assert!(LeapSeconds::from_dates(&[20261232]).is_err());
+        assert!(LeapSeconds::from_dates(&[19711231]).is_err());
+        assert!(LeapSeconds::parse("2026-12-31").is_err());
+        assert!(LeapSeconds::load("does/not/exist.txt").is_err());
+
+        // The vendored example file only repeats a built-in date.
+        let vendored = LeapSeconds::load("src/swisseph/seleapsec.txt").unwrap();
+        assert_eq!(vendored, LeapSeconds::builtin());
+    }
+
+    #[test]
+    fn leap_seconds_and_delta_t_change_utc_conversion() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping leap_seconds_and_delta_t_change_utc_conversion: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        let default = Ephemeris::new(ephe_path).unwrap();
+        let builtin = default.clone().with_leap_seconds(LeapSeconds::builtin());
+        let extended = default
+            .clone()
+            .with_leap_seconds(LeapSeconds::parse("20261231").unwrap());
+
+        // The Rust conversion with the built-in table matches swe_utc_to_jd.
+        let noon = birth(2027, 6, 1, 12, 0, 0.0);
+        let swe = default.julian_day(&noon).unwrap();
+        let same = builtin.julian_day(&noon).unwrap();
+        assert!((swe.tt() - same.tt()).abs() * 86_400.0 < 1e-4);
+        assert!((swe.ut() - same.ut()).abs() * 86_400.0 < 1e-4);
+
+        // One more leap second puts the same UTC one second later in TT and UT1.
+        let later = extended.julian_day(&noon).unwrap();
+        assert!(((later.tt() - same.tt()) * 86_400.0 - 1.0).abs() < 1e-4);
+        assert!(((later.ut() - same.ut()) * 86_400.0 - 1.0).abs() < 1e-4);
+
+        let leap = birth(2026, 12, 31, 23, 59, 60.5);
+        assert!(default.julian_day(&leap).is_err());
+        let leap_jd = extended.julian_day(&leap).unwrap();
+        // Far-off years do not overflow the table lookup.
+        let far = extended
+            .julian_day(&birth(300_000, 1, 1, 0, 0, 0.0))
+            .unwrap();
+        let utc = extended.to_utc(far, Calendar::Gregorian).unwrap();
+        assert_eq!((utc.year, utc.month, utc.day), (300_000, 1, 1));
+
+        // Converting back uses the same table, so both moments round-trip.
+        let utc = extended.to_utc(leap_jd, Calendar::Gregorian).unwrap();
+        assert_eq!(
+            (utc.year, utc.day, utc.hour, utc.minute),
+            (2026, 31, 23, 59)
+        );
+        assert!((utc.second - 60.5).abs() < 1e-3);
+        let utc = extended.to_utc(later, Calendar::Gregorian).unwrap();
+        assert_eq!((utc.year, utc.month, utc.day, utc.hour), (2027, 6, 1, 12));
+        assert!(utc.second.abs() < 1e-3);
+        // From UT1, the context's Delta T stands in for the table; both agree within a second.
+        let via_ut = extended.ut_to_utc(later, Calendar::Gregorian).unwrap();
+        let seconds = via_ut.hour as f64 * 3600.0 + via_ut.minute as f64 * 60.0 + via_ut.second;
+        assert_eq!((via_ut.year, via_ut.month, via_ut.day), (2027, 6, 1));
+        assert!((seconds - 43_200.0).abs() < 1.0, "{via_ut:?}");
+        let utc = default.to_utc(later, Calendar::Gregorian).unwrap();
+        assert!((utc.second - 1.0).abs() < 1e-3);
+
+        // A user-defined Delta T replaces the model.
+        let fixed = default.clone().with_delta_t(100.0);
+        let jd = fixed.julian_day(&birth(1990, 1, 1, 0, 0, 0.0)).unwrap();
+        assert!((jd.delta_t_seconds() - 100.0).abs() < 1e-4);
+        let from_ut = fixed.julian_day_from_ut(jd.ut()).unwrap();
+        assert!((from_ut.delta_t_seconds() - 100.0).abs() < 1e-4);
+        let from_tt = fixed.julian_day_from_tt(jd.tt()).unwrap();
+        assert!((from_tt.ut() - jd.ut()).abs() * 86_400.0 < 1e-4);
+
+        // The Moon's tidal acceleration shapes Delta T in the distant past.
+        let medieval = birth(1000, 1, 1, 0, 0, 0.0);
+        let modelled = default.julian_day(&medieval).unwrap();
+        let adjusted = default
+            .clone()
+            .with_tidal_acceleration(-23.8946)
+            .julian_day(&medieval)
+            .unwrap();
+        assert!((modelled.delta_t_seconds() - adjusted.delta_t_seconds()).abs() > 10.0);
+        // Settings do not leak into contexts without them.
+        let again = default.julian_day(&medieval).unwrap();
+        assert_eq!(again, modelled);
+    }
+}
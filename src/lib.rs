@@ -1,202 +1,131 @@
+use error::ephemeris_error;
 use libc::{c_char, c_int};
-use std::{
-    ffi::CString,
-    sync::{Mutex, OnceLock},
-};
-use thiserror::Error;
-
-mod ffi {
-    use libc::{c_char, c_double, c_int};
-
-    pub const SE_SUN: c_int = 0;
-    pub const SE_MOON: c_int = 1;
-    pub const SE_ASC: usize = 0;
-    pub const SE_GREG_CAL: c_int = 1;
-    pub const SEFLG_SWIEPH: c_int = 2;
-    pub const AS_MAXCH: usize = 256;
-
-    extern "C" {
-        pub fn swe_set_ephe_path(path: *const c_char);
 
-        pub fn swe_utc_to_jd(
-            year: c_int,
-            month: c_int,
-            day: c_int,
-            hour: c_int,
-            minute: c_int,
-            second: c_double,
-            gregflag: c_int,
-            dret: *mut c_double,
-            serr: *mut c_char,
-        ) -> c_int;
-
-        pub fn swe_calc_ut(
-            tjd_ut: c_double,
-            ipl: c_int,
-            iflag: c_int,
-            xx: *mut c_double,
-            serr: *mut c_char,
-        ) -> c_int;
-
-        pub fn swe_houses_ex(
-            tjd_ut: c_double,
-            iflag: c_int,
-            geolat: c_double,
-            geolon: c_double,
-            hsys: c_int,
-            cusps: *mut c_double,
-            ascmc: *mut c_double,
-        ) -> c_int;
-    }
-}
+mod asteroids;
+mod birth;
+mod calendar;
+mod chart;
+mod ephemeris;
+mod error;
+mod ffi;
+mod fixed_stars;
+mod houses;
+mod julian;
+mod leap_seconds;
+mod sidereal;
+mod sign;
+mod timezone;
+
+pub use asteroids::{
+    asteroid_names, calculate_asteroid, calculate_asteroid_by_name, AsteroidNames,
+    ASTEROID_NAMES_FILE,
+};
+pub use birth::BirthDataBuilder;
+pub use calendar::{Calendar, GregorianAdoption};
+pub use chart::{
+    calculate_body, calculate_full_chart, calculate_full_chart_with, Body, BodyPosition,
+    CenterMode, ChartOptions, FullChart, MotionState,
+};
+pub use ephemeris::{
+    set_ephe_path, set_ephemeris_backend, Ephemeris, EphemerisBackend, EphemerisSource,
+};
+pub use error::AstroError;
+pub use fixed_stars::{
+    calculate_star_conjunctions, fixed_star, star_catalogue, ChartPoint, FixedStarPosition,
+    StarCatalogue, StarConjunction, StarConjunctionOptions, StarLatitude, StarRecord, STAR_FILE,
+};
+pub use houses::{
+    calculate_houses, calculate_houses_with, AnglePoint, Angles, HouseResult, HouseSystem,
+    PolarFallback,
+};
+pub use julian::{JulianDay, UtcDateTime};
+pub use leap_seconds::LeapSeconds;
+pub use sidereal::{ayanamsa_value, Ayanamsa, Zodiac};
+pub use sign::{Carry, Element, Modality, Polarity, Rounding, Sign, ZodiacPosition};
+pub use timezone::{Disambiguation, LocalBirthData};
 
-/// Basic data for birth info in UTC.
+/// Basic data for birth info in UTC. Use `LocalBirthData` to start from local wall-clock time.
 #[derive(Debug, Clone)]
 pub struct BirthData {
     pub year: i32,
     pub month: i32,
     pub day: i32,
-    pub hour: i32,   // 0-23, UTC
-    pub minute: i32, // 0-59
-    pub second: f64, // 0.0-59.999
-    pub lat: f64,    // latitude in degrees (+N, -S)
-    pub lon: f64,    // longitude in degrees (+E, -W)
+    pub hour: i32,               // 0-23, UTC
+    pub minute: i32,             // 0-59
+    pub second: f64,             // 0.0-59.999
+    pub lat: f64,                // latitude in degrees (+N, -S)
+    pub lon: f64,                // longitude in degrees (+E, -W)
+    pub altitude_m: Option<f64>, // elevation above sea level in meters; None means 0
+    pub calendar: Calendar,      // calendar the date is written in
 }
 
 /// Core chart with three main indicators.
 #[derive(Debug, Clone)]
 pub struct CoreChart {
-    pub sun_sign: String, // "aries", "taurus", ...
-    pub moon_sign: String,
-    pub asc_sign: String,
-}
-
-#[derive(Debug, Error)]
-pub enum AstroError {
-    #[error("Swiss Ephemeris error: {0}")]
-    EphemerisError(String),
-
-    #[error("Invalid input: {0}")]
-    InvalidInput(String),
-}
-
-static EPHE_PATH: OnceLock<Mutex<String>> = OnceLock::new();
-
-/// Override the Swiss Ephemeris data path. Defaults to the current directory when unset.
-pub fn set_ephe_path(path: &str) {
-    let mut guard = ephe_path_store()
-        .lock()
-        .expect("ephemeris path mutex poisoned");
-    *guard = path.to_string();
-    let c_path = CString::new(path).expect("ephemeris path must not contain interior null bytes");
-    unsafe {
-        ffi::swe_set_ephe_path(c_path.as_ptr());
-    }
+    pub sun_sign: Sign,
+    pub moon_sign: Sign,
+    pub asc_sign: Sign,
 }
 
 /// Calculate the Sun, Moon, and Ascendant signs for the given birth data.
 pub fn calculate_core_chart(birth: &BirthData) -> Result<CoreChart, AstroError> {
-    apply_ephe_path()?;
-    let tjd_ut = julian_day_ut(birth)?;
+    Ephemeris::global().core_chart(birth)
+}
+
+pub(crate) fn core_chart(
+    ephe_flag: c_int,
+    jd: JulianDay,
+    birth: &BirthData,
+) -> Result<CoreChart, AstroError> {
+    let tjd_ut = jd.ut();
 
-    let sun_long = body_longitude(tjd_ut, ffi::SE_SUN)?;
-    let moon_long = body_longitude(tjd_ut, ffi::SE_MOON)?;
-    let asc_long = ascendant_longitude(tjd_ut, birth.lat, birth.lon)?;
+    let sun_long = body_longitude(tjd_ut, ffi::SE_SUN, ephe_flag)?;
+    let moon_long = body_longitude(tjd_ut, ffi::SE_MOON, ephe_flag)?;
+    let asc_long = ascendant_longitude(tjd_ut, birth.lat, birth.lon, ephe_flag)?;
 
     Ok(CoreChart {
-        sun_sign: sign_name_from_longitude(sun_long),
-        moon_sign: sign_name_from_longitude(moon_long),
-        asc_sign: sign_name_from_longitude(asc_long),
+        sun_sign: Sign::from_longitude(sun_long),
+        moon_sign: Sign::from_longitude(moon_long),
+        asc_sign: Sign::from_longitude(asc_long),
     })
 }
 
-fn ephe_path_store() -> &'static Mutex<String> {
-    EPHE_PATH.get_or_init(|| Mutex::new(String::new()))
+fn body_longitude(tjd_ut: f64, ipl: c_int, ephe_flag: c_int) -> Result<f64, AstroError> {
+    let calc = calc_ut(tjd_ut, ipl, ephe_flag)?;
+    Ok(calc.xx[0])
 }
 
-fn apply_ephe_path() -> Result<(), AstroError> {
-    let guard = ephe_path_store()
-        .lock()
-        .map_err(|_| AstroError::InvalidInput("ephemeris path lock poisoned".to_string()))?;
-    let c_path = CString::new(guard.as_str())
-        .map_err(|_| AstroError::InvalidInput("ephemeris path contains null byte".into()))?;
-    unsafe {
-        ffi::swe_set_ephe_path(c_path.as_ptr());
-    }
-    Ok(())
+/// Raw `swe_calc_ut` output.
+pub(crate) struct Calc {
+    pub xx: [f64; 6],            // longitude, latitude, distance and their speeds
+    pub retflag: c_int,          // flags actually used, including the ephemeris that answered
+    pub warning: Option<String>, // e.g. why Swiss Ephemeris fell back to Moshier
 }
 
-fn julian_day_ut(birth: &BirthData) -> Result<f64, AstroError> {
-    let mut dret = [0f64; 2];
-    let mut serr = [0 as c_char; ffi::AS_MAXCH];
-    let rc = unsafe {
-        ffi::swe_utc_to_jd(
-            birth.year as c_int,
-            birth.month as c_int,
-            birth.day as c_int,
-            birth.hour as c_int,
-            birth.minute as c_int,
-            birth.second,
-            ffi::SE_GREG_CAL,
-            dret.as_mut_ptr(),
-            serr.as_mut_ptr(),
-        )
-    };
-    if rc < 0 {
-        return Err(AstroError::EphemerisError(error_string(&serr)));
-    }
-    // dret[1] = UT
-    Ok(dret[1])
-}
-
-fn body_longitude(tjd_ut: f64, ipl: c_int) -> Result<f64, AstroError> {
+pub(crate) fn calc_ut(tjd_ut: f64, ipl: c_int, iflag: c_int) -> Result<Calc, AstroError> {
     let mut xx = [0f64; 6];
     let mut serr = [0 as c_char; ffi::AS_MAXCH];
-    let rc = unsafe {
-        ffi::swe_calc_ut(
-            tjd_ut,
-            ipl,
-            ffi::SEFLG_SWIEPH,
-            xx.as_mut_ptr(),
-            serr.as_mut_ptr(),
-        )
-    };
-    if rc < 0 {
-        return Err(AstroError::EphemerisError(error_string(&serr)));
-    }
-    Ok(xx[0])
-}
-
-fn ascendant_longitude(tjd_ut: f64, lat: f64, lon: f64) -> Result<f64, AstroError> {
-    let mut cusps = [0f64; 13];
-    let mut ascmc = [0f64; 10];
-    let rc = unsafe {
-        ffi::swe_houses_ex(
-            tjd_ut,
-            ffi::SEFLG_SWIEPH,
-            lat,
-            lon,
-            'P' as c_int,
-            cusps.as_mut_ptr(),
-            ascmc.as_mut_ptr(),
-        )
-    };
+    let rc = unsafe { ffi::swe_calc_ut(tjd_ut, ipl, iflag, xx.as_mut_ptr(), serr.as_mut_ptr()) };
     if rc < 0 {
-        return Err(AstroError::EphemerisError(
-            "failed to compute ascendant".to_string(),
-        ));
+        return Err(ephemeris_error(&serr));
     }
-    Ok(ascmc[ffi::SE_ASC])
+    let warning = (serr[0] != 0).then(|| error::error_string(&serr));
+    Ok(Calc {
+        xx,
+        retflag: rc,
+        warning,
+    })
 }
 
-fn error_string(buf: &[c_char]) -> String {
-    let nul = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
-    let bytes: Vec<u8> = buf[..nul].iter().map(|&c| c as u8).collect();
-    if bytes.is_empty() {
-        "unknown Swiss Ephemeris error".to_string()
-    } else {
-        String::from_utf8_lossy(&bytes).into_owned()
-    }
+fn ascendant_longitude(
+    tjd_ut: f64,
+    lat: f64,
+    lon: f64,
+    ephe_flag: c_int,
+) -> Result<f64, AstroError> {
+    // The ascendant is the same in every house system, so a polar Porphyry fallback is fine.
+    let houses = houses::house_cusps(tjd_ut, lat, lon, HouseSystem::Placidus, ephe_flag)?;
+    Ok(houses.angles.ascendant.longitude)
 }
 
 const ZODIAC_SIGNS: [&str; 12] = [
@@ -214,13 +143,9 @@ const ZODIAC_SIGNS: [&str; 12] = [
     "pisces",
 ];
 
+/// Lowercase sign name for an ecliptic longitude; see `Sign::from_longitude` for the typed form.
 pub fn sign_name_from_longitude(lon: f64) -> String {
-    let mut norm = lon % 360.0;
-    if norm < 0.0 {
-        norm += 360.0;
-    }
-    let index = (norm / 30.0).floor() as usize % ZODIAC_SIGNS.len();
-    ZODIAC_SIGNS[index].to_string()
+    Sign::from_longitude(lon).name().to_string()
 }
 
 #[cfg(test)]
@@ -248,12 +173,55 @@ mod tests {
             second: 0.0,
             lat: 0.0,
             lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
         };
 
         let chart = calculate_core_chart(&birth).expect("chart should compute");
 
-        assert!(ZODIAC_SIGNS.contains(&chart.sun_sign.as_str()));
-        assert!(ZODIAC_SIGNS.contains(&chart.moon_sign.as_str()));
-        assert!(ZODIAC_SIGNS.contains(&chart.asc_sign.as_str()));
+        assert_eq!(chart.sun_sign, Sign::Capricorn);
+        assert!(ZODIAC_SIGNS.contains(&chart.moon_sign.name()));
+        assert!(ZODIAC_SIGNS.contains(&chart.asc_sign.name()));
+        assert_eq!(sign_name_from_longitude(280.0), "capricorn");
+    }
+
+    #[test]
+    fn reports_structured_errors() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping reports_structured_errors: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 1990,
+            month: 13,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        // Caught by `BirthData::validate` before reaching the C library.
+        let err = calculate_core_chart(&birth).unwrap_err();
+        assert!(matches!(err, AstroError::InvalidDate(_)), "{err}");
+
+        let far_future = BirthData {
+            year: 20000,
+            month: 1,
+            ..birth
+        };
+        // No Swiss Ephemeris file covers year 20000, so the error names the file it wanted.
+        let err = calculate_core_chart(&far_future).unwrap_err();
+        assert!(
+            matches!(err, AstroError::MissingEphemerisFile { ref file, .. } if file == "sepl_198.se1"),
+            "{err}"
+        );
     }
 }
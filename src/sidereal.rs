@@ -0,0 +1,387 @@
+use libc::{c_char, c_int};
+use std::ffi::CStr;
+
+use crate::error::ephemeris_error;
+use crate::ffi;
+use crate::{AstroError, BirthData, Ephemeris};
+
+/// Swiss Ephemeris ayanamsas: the predefined `SE_SIDM_*` constants plus a user-defined one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ayanamsa {
+    FaganBradley,
+    Lahiri,
+    DeLuce,
+    Raman,
+    Ushashashi,
+    Krishnamurti,
+    DjwhalKhul,
+    Yukteshwar,
+    JnBhasin,
+    BabylonianKugler1,
+    BabylonianKugler2,
+    BabylonianKugler3,
+    BabylonianHuber,
+    BabylonianEtaPiscium,
+    Aldebaran15Tau,
+    Hipparchos,
+    Sassanian,
+    GalacticCenter0Sag,
+    J2000,
+    J1900,
+    B1950,
+    Suryasiddhanta,
+    SuryasiddhantaMeanSun,
+    Aryabhata,
+    AryabhataMeanSun,
+    SsRevati,
+    SsCitra,
+    TrueCitra,
+    TrueRevati,
+    TruePushya,
+    GalacticCenterGilBrand,
+    GalacticEquatorIau1958,
+    GalacticEquatorTrue,
+    GalacticEquatorMula,
+    GalacticAlignmentMardyks,
+    TrueMula,
+    GalacticCenterMulaWilhelm,
+    Aryabhata522,
+    BabylonianBritton,
+    TrueSheoran,
+    GalacticCenterCochrane,
+    GalacticEquatorFiorenza,
+    ValensMoon,
+    Lahiri1940,
+    LahiriVp285,
+    KrishnamurtiVp291,
+    LahiriIcrc,
+    /// User-defined ayanamsa (`SE_SIDM_USER`): `ayan_t0` degrees at Julian day `t0`,
+    /// where `t0` is UT if `t0_is_ut` and TT otherwise.
+    Custom {
+        t0: f64,
+        ayan_t0: f64,
+        t0_is_ut: bool,
+    },
+}
+
+impl Ayanamsa {
+    /// Every predefined ayanamsa, excluding `Custom`.
+    pub const ALL: [Ayanamsa; 47] = [
+        Ayanamsa::FaganBradley,
+        Ayanamsa::Lahiri,
+        Ayanamsa::DeLuce,
+        Ayanamsa::Raman,
+        Ayanamsa::Ushashashi,
+        Ayanamsa::Krishnamurti,
+        Ayanamsa::DjwhalKhul,
+        Ayanamsa::Yukteshwar,
+        Ayanamsa::JnBhasin,
+        Ayanamsa::BabylonianKugler1,
+        Ayanamsa::BabylonianKugler2,
+        Ayanamsa::BabylonianKugler3,
+        Ayanamsa::BabylonianHuber,
+        Ayanamsa::BabylonianEtaPiscium,
+        Ayanamsa::Aldebaran15Tau,
+        Ayanamsa::Hipparchos,
+        Ayanamsa::Sassanian,
+        Ayanamsa::GalacticCenter0Sag,
+        Ayanamsa::J2000,
+        Ayanamsa::J1900,
+        Ayanamsa::B1950,
+        Ayanamsa::Suryasiddhanta,
+        Ayanamsa::SuryasiddhantaMeanSun,
+        Ayanamsa::Aryabhata,
+        Ayanamsa::AryabhataMeanSun,
+        Ayanamsa::SsRevati,
+        Ayanamsa::SsCitra,
+        Ayanamsa::TrueCitra,
+        Ayanamsa::TrueRevati,
+        Ayanamsa::TruePushya,
+        Ayanamsa::GalacticCenterGilBrand,
+        Ayanamsa::GalacticEquatorIau1958,
+        Ayanamsa::GalacticEquatorTrue,
+        Ayanamsa::GalacticEquatorMula,
+        Ayanamsa::GalacticAlignmentMardyks,
+        Ayanamsa::TrueMula,
+        Ayanamsa::GalacticCenterMulaWilhelm,
+        Ayanamsa::Aryabhata522,
+        Ayanamsa::BabylonianBritton,
+        Ayanamsa::TrueSheoran,
+        Ayanamsa::GalacticCenterCochrane,
+        Ayanamsa::GalacticEquatorFiorenza,
+        Ayanamsa::ValensMoon,
+        Ayanamsa::Lahiri1940,
+        Ayanamsa::LahiriVp285,
+        Ayanamsa::KrishnamurtiVp291,
+        Ayanamsa::LahiriIcrc,
+    ];
+
+    /// The `SE_SIDM_*` constant passed to `swe_set_sid_mode`.
+    pub fn code(self) -> i32 {
+        match self {
+            Ayanamsa::FaganBradley => 0,
+            Ayanamsa::Lahiri => 1,
+            Ayanamsa::DeLuce => 2,
+            Ayanamsa::Raman => 3,
+            Ayanamsa::Ushashashi => 4,
+            Ayanamsa::Krishnamurti => 5,
+            Ayanamsa::DjwhalKhul => 6,
+            Ayanamsa::Yukteshwar => 7,
+            Ayanamsa::JnBhasin => 8,
+            Ayanamsa::BabylonianKugler1 => 9,
+            Ayanamsa::BabylonianKugler2 => 10,
+            Ayanamsa::BabylonianKugler3 => 11,
+            Ayanamsa::BabylonianHuber => 12,
+            Ayanamsa::BabylonianEtaPiscium => 13,
+            Ayanamsa::Aldebaran15Tau => 14,
+            Ayanamsa::Hipparchos => 15,
+            Ayanamsa::Sassanian => 16,
+            Ayanamsa::GalacticCenter0Sag => 17,
+            Ayanamsa::J2000 => 18,
+            Ayanamsa::J1900 => 19,
+            Ayanamsa::B1950 => 20,
+            Ayanamsa::Suryasiddhanta => 21,
+            Ayanamsa::SuryasiddhantaMeanSun => 22,
+            Ayanamsa::Aryabhata => 23,
+            Ayanamsa::AryabhataMeanSun => 24,
+            Ayanamsa::SsRevati => 25,
+            Ayanamsa::SsCitra => 26,
+            Ayanamsa::TrueCitra => 27,
+            Ayanamsa::TrueRevati => 28,
+            Ayanamsa::TruePushya => 29,
+            Ayanamsa::GalacticCenterGilBrand => 30,
+            Ayanamsa::GalacticEquatorIau1958 => 31,
+            Ayanamsa::GalacticEquatorTrue => 32,
+            Ayanamsa::GalacticEquatorMula => 33,
+            Ayanamsa::GalacticAlignmentMardyks => 34,
+            Ayanamsa::TrueMula => 35,
+            Ayanamsa::GalacticCenterMulaWilhelm => 36,
+            Ayanamsa::Aryabhata522 => 37,
+            Ayanamsa::BabylonianBritton => 38,
+            Ayanamsa::TrueSheoran => 39,
+            Ayanamsa::GalacticCenterCochrane => 40,
+            Ayanamsa::GalacticEquatorFiorenza => 41,
+            Ayanamsa::ValensMoon => 42,
+            Ayanamsa::Lahiri1940 => 43,
+            Ayanamsa::LahiriVp285 => 44,
+            Ayanamsa::KrishnamurtiVp291 => 45,
+            Ayanamsa::LahiriIcrc => 46,
+            Ayanamsa::Custom { .. } => ffi::SE_SIDM_USER,
+        }
+    }
+
+    /// Look up a predefined ayanamsa; `SE_SIDM_USER` has no parameters and yields `None`.
+    pub fn from_code(code: i32) -> Option<Ayanamsa> {
+        Ayanamsa::ALL.into_iter().find(|a| a.code() == code)
+    }
+
+    /// Name as reported by Swiss Ephemeris, e.g. `"Lahiri"`.
+    pub fn name(self) -> String {
+        if let Ayanamsa::Custom { .. } = self {
+            return "User-defined".to_string();
+        }
+        let name = unsafe { ffi::swe_get_ayanamsa_name(self.code() as c_int) };
+        if name.is_null() {
+            return format!("{:?}", self);
+        }
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Check that a `Custom` ayanamsa has a finite epoch and an initial value within ±360°.
+    pub fn validate(&self) -> Result<(), AstroError> {
+        if let Ayanamsa::Custom { t0, ayan_t0, .. } = *self {
+            if !t0.is_finite() {
+                return Err(AstroError::InvalidInput(format!(
+                    "custom ayanamsa epoch must be a finite Julian day, got {}",
+                    t0
+                )));
+            }
+            if !ayan_t0.is_finite() || ayan_t0.abs() > 360.0 {
+                return Err(AstroError::InvalidInput(format!(
+                    "custom ayanamsa value must be within ±360°, got {}",
+                    ayan_t0
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply(self) -> Result<(), AstroError> {
+        self.validate()?;
+        let (sid_mode, t0, ayan_t0) = match self {
+            Ayanamsa::Custom {
+                t0,
+                ayan_t0,
+                t0_is_ut,
+            } => {
+                let ut_bit = if t0_is_ut { ffi::SE_SIDBIT_USER_UT } else { 0 };
+                (ffi::SE_SIDM_USER | ut_bit, t0, ayan_t0)
+            }
+            _ => (self.code() as c_int, 0.0, 0.0),
+        };
+        unsafe {
+            ffi::swe_set_sid_mode(sid_mode, t0, ayan_t0);
+        }
+        Ok(())
+    }
+}
+
+/// Zodiac used for longitudes and house cusps.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Zodiac {
+    #[default]
+    Tropical,
+    Sidereal(Ayanamsa),
+}
+
+impl Zodiac {
+    /// Set the C library's sidereal mode and return the flags to add to each calculation.
+    pub(crate) fn apply(self) -> Result<c_int, AstroError> {
+        match self {
+            Zodiac::Tropical => Ok(0),
+            Zodiac::Sidereal(ayanamsa) => {
+                ayanamsa.apply()?;
+                Ok(ffi::SEFLG_SIDEREAL)
+            }
+        }
+    }
+}
+
+/// Ayanamsa value in degrees at the given (UTC) birth time, including nutation.
+pub fn ayanamsa_value(birth: &BirthData, ayanamsa: Ayanamsa) -> Result<f64, AstroError> {
+    Ephemeris::global().ayanamsa(birth, ayanamsa)
+}
+
+pub(crate) fn ayanamsa_ut(
+    tjd_ut: f64,
+    ayanamsa: Ayanamsa,
+    iflag: c_int,
+) -> Result<f64, AstroError> {
+    ayanamsa.apply()?;
+    let mut daya = 0f64;
+    let mut serr = [0 as c_char; ffi::AS_MAXCH];
+    let rc = unsafe { ffi::swe_get_ayanamsa_ex_ut(tjd_ut, iflag, &mut daya, serr.as_mut_ptr()) };
+    if rc < 0 {
+        return Err(ephemeris_error(&serr));
+    }
+    Ok(daya)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_full_chart, calculate_full_chart_with, set_ephe_path};
+    use crate::{Body, Calendar, ChartOptions, Sign};
+    use std::path::Path;
+
+    #[test]
+    fn sidereal_chart_subtracts_ayanamsa() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping sidereal_chart_subtracts_ayanamsa: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let birth = BirthData {
+            year: 2000,
+            month: 1,
+            day: 1,
+            hour: 12,
+            minute: 0,
+            second: 0.0,
+            lat: 28.6139,
+            lon: 77.2090,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+
+        // Lahiri ayanamsa at J2000 is about 23°51'.
+        let lahiri = ayanamsa_value(&birth, Ayanamsa::Lahiri).unwrap();
+        assert!((lahiri - 23.85).abs() < 0.02);
+        assert_eq!(Ayanamsa::Lahiri.name(), "Lahiri");
+        assert_eq!(Ayanamsa::from_code(27), Some(Ayanamsa::TrueCitra));
+
+        let tropical = calculate_full_chart(&birth).unwrap();
+        let options = ChartOptions {
+            zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+            ..ChartOptions::default()
+        };
+        let sidereal = calculate_full_chart_with(&birth, &options).unwrap();
+        let trop_sun = tropical.get(Body::Sun).unwrap();
+        let sid_sun = sidereal.get(Body::Sun).unwrap();
+        let diff = (trop_sun.longitude - sid_sun.longitude).rem_euclid(360.0);
+        assert!((diff - lahiri).abs() < 0.01);
+        assert_eq!(trop_sun.sign, Sign::Capricorn);
+        assert_eq!(sid_sun.sign, Sign::Sagittarius);
+
+        for ayanamsa in Ayanamsa::ALL {
+            ayanamsa_value(&birth, ayanamsa).expect("ayanamsa should compute");
+        }
+    }
+
+    #[test]
+    fn custom_ayanamsa_round_trips_at_epoch() {
+        let ephe_path = "src/swisseph/ephe";
+        if !Path::new(ephe_path).exists() {
+            eprintln!(
+                "skipping custom_ayanamsa_round_trips_at_epoch: missing ephemeris data at {}",
+                ephe_path
+            );
+            return;
+        }
+        set_ephe_path(ephe_path);
+        let t0 = 2_451_545.0; // J2000
+        let ayanamsa = Ayanamsa::Custom {
+            t0,
+            ayan_t0: 23.5,
+            t0_is_ut: true,
+        };
+        {
+            let ephemeris = Ephemeris::new(ephe_path).unwrap();
+            let session = ephemeris.session().unwrap();
+            // Without nutation the mean ayanamsa at t0 is exactly the configured value.
+            let mean = session.ephe_flag | ffi::SEFLG_NONUT;
+            let at_t0 = ayanamsa_ut(t0, ayanamsa, mean).unwrap();
+            assert!((at_t0 - 23.5).abs() < 1e-9, "{}", at_t0);
+            // A century later precession has added roughly 1.4°.
+            let later = ayanamsa_ut(t0 + 36_525.0, ayanamsa, mean).unwrap();
+            assert!((later - 24.9).abs() < 0.05);
+        }
+
+        let invalid = Ayanamsa::Custom {
+            t0: f64::NAN,
+            ayan_t0: 23.5,
+            t0_is_ut: false,
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(AstroError::InvalidInput(_))
+        ));
+        let options = ChartOptions {
+            zodiac: Zodiac::Sidereal(Ayanamsa::Custom {
+                t0,
+                ayan_t0: 400.0,
+                t0_is_ut: false,
+            }),
+            ..ChartOptions::default()
+        };
+        let birth = BirthData {
+            year: 2000,
+            month: 1,
+            day: 1,
+            hour: 12,
+            minute: 0,
+            second: 0.0,
+            lat: 0.0,
+            lon: 0.0,
+            altitude_m: None,
+            calendar: Calendar::Gregorian,
+        };
+        assert!(calculate_full_chart_with(&birth, &options).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use libc::{c_double, c_int};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ffi, AstroError, Body};
+
+/// The twelve tropical or sidereal zodiac signs, 30° each starting from Aries at 0°.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Sign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+/// Classical element of a sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Element {
+    Fire,
+    Earth,
+    Air,
+    Water,
+}
+
+/// Cardinal, fixed, or mutable quality of a sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modality {
+    Cardinal,
+    Fixed,
+    Mutable,
+}
+
+/// Positive (fire, air) or negative (earth, water) signs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    Positive,
+    Negative,
+}
+
+impl Sign {
+    /// All signs in zodiacal order.
+    pub const ALL: [Sign; 12] = [
+        Sign::Aries,
+        Sign::Taurus,
+        Sign::Gemini,
+        Sign::Cancer,
+        Sign::Leo,
+        Sign::Virgo,
+        Sign::Libra,
+        Sign::Scorpio,
+        Sign::Sagittarius,
+        Sign::Capricorn,
+        Sign::Aquarius,
+        Sign::Pisces,
+    ];
+
+    /// Sign containing an ecliptic longitude in degrees; any value is normalized to 0-360.
+    pub fn from_longitude(lon: f64) -> Sign {
+        let norm = lon.rem_euclid(360.0);
+        Sign::from_index((norm / 30.0).floor() as usize % 12)
+    }
+
+    /// Sign at a 0-based position in zodiacal order (Aries = 0); wraps past Pisces.
+    pub fn from_index(index: usize) -> Sign {
+        Sign::ALL[index % 12]
+    }
+
+    /// 0-based position in zodiacal order (Aries = 0, Pisces = 11).
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Lowercase name, e.g. `"aries"`.
+    pub fn name(self) -> &'static str {
+        crate::ZODIAC_SIGNS[self.index()]
+    }
+
+    /// Ecliptic longitude where the sign begins.
+    pub fn start_longitude(self) -> f64 {
+        self.index() as f64 * 30.0
+    }
+
+    pub fn element(self) -> Element {
+        match self.index() % 4 {
+            0 => Element::Fire,
+            1 => Element::Earth,
+            2 => Element::Air,
+            _ => Element::Water,
+        }
+    }
+
+    pub fn modality(self) -> Modality {
+        match self.index() % 3 {
+            0 => Modality::Cardinal,
+            1 => Modality::Fixed,
+            _ => Modality::Mutable,
+        }
+    }
+
+    pub fn polarity(self) -> Polarity {
+        match self.element() {
+            Element::Fire | Element::Air => Polarity::Positive,
+            Element::Earth | Element::Water => Polarity::Negative,
+        }
+    }
+
+    /// Modern ruling planet (Pluto for Scorpio, Uranus for Aquarius, Neptune for Pisces).
+    pub fn ruler(self) -> Body {
+        match self {
+            Sign::Scorpio => Body::Pluto,
+            Sign::Aquarius => Body::Uranus,
+            Sign::Pisces => Body::Neptune,
+            _ => self.traditional_ruler(),
+        }
+    }
+
+    /// Ruling planet in the traditional seven-planet scheme.
+    pub fn traditional_ruler(self) -> Body {
+        match self {
+            Sign::Aries | Sign::Scorpio => Body::Mars,
+            Sign::Taurus | Sign::Libra => Body::Venus,
+            Sign::Gemini | Sign::Virgo => Body::Mercury,
+            Sign::Cancer => Body::Moon,
+            Sign::Leo => Body::Sun,
+            Sign::Sagittarius | Sign::Pisces => Body::Jupiter,
+            Sign::Capricorn | Sign::Aquarius => Body::Saturn,
+        }
+    }
+
+    pub fn next(self) -> Sign {
+        Sign::from_index(self.index() + 1)
+    }
+
+    pub fn previous(self) -> Sign {
+        Sign::from_index(self.index() + 11)
+    }
+
+    pub fn opposite(self) -> Sign {
+        Sign::from_index(self.index() + 6)
+    }
+}
+
+impl fmt::Display for Sign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Sign {
+    type Err = AstroError;
+
+    /// Parse a sign name, ignoring case and surrounding whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Sign::ALL
+            .into_iter()
+            .find(|sign| sign.name() == name)
+            .ok_or_else(|| AstroError::InvalidInput(format!("unknown zodiac sign '{s}'")))
+    }
+}
+
+/// Smallest unit kept when splitting a longitude into degrees, minutes, and seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Truncate to whole seconds and report the remainder in `ZodiacPosition::fraction`.
+    #[default]
+    Truncate,
+    Second,
+    Minute,
+    Degree,
+}
+
+/// What rounding may carry into when a value sits just below a boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Carry {
+    /// Round normally but truncate instead of reaching 30°, so 29°59'59.7" stays in its sign.
+    #[default]
+    KeepSign,
+    /// Truncate instead of rounding up into the next whole degree.
+    KeepDegree,
+    /// Allow rounding into the next sign, e.g. 29°59'59.7" Cancer becomes 0°00'00" Leo.
+    NextSign,
+}
+
+/// A longitude expressed as sign, degree, minute, and second, e.g. 23°14'05" Cancer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZodiacPosition {
+    pub sign: Sign,
+    pub degree: u32,   // 0-29
+    pub minute: u32,   // 0-59
+    pub second: u32,   // 0-59
+    pub fraction: f64, // fraction of a second, 0 when rounded
+}
+
+impl ZodiacPosition {
+    /// Split a longitude without rounding; any value is normalized to 0-360.
+    pub fn from_longitude(lon: f64) -> ZodiacPosition {
+        ZodiacPosition::rounded(lon, Rounding::Truncate, Carry::KeepSign)
+    }
+
+    /// Split a longitude with `swe_split_deg`, rounding to the given unit.
+    pub fn rounded(lon: f64, rounding: Rounding, carry: Carry) -> ZodiacPosition {
+        let mut flags = ffi::SE_SPLIT_DEG_ZODIACAL;
+        flags |= match rounding {
+            Rounding::Truncate => 0,
+            Rounding::Second => ffi::SE_SPLIT_DEG_ROUND_SEC,
+            Rounding::Minute => ffi::SE_SPLIT_DEG_ROUND_MIN,
+            Rounding::Degree => ffi::SE_SPLIT_DEG_ROUND_DEG,
+        };
+        flags |= match carry {
+            Carry::KeepSign => ffi::SE_SPLIT_DEG_KEEP_SIGN,
+            Carry::KeepDegree => ffi::SE_SPLIT_DEG_KEEP_DEG,
+            Carry::NextSign => 0,
+        };
+        let (mut deg, mut min, mut sec, mut sign) = (0 as c_int, 0 as c_int, 0 as c_int, 0);
+        let mut fraction: c_double = 0.0;
+        unsafe {
+            ffi::swe_split_deg(
+                lon.rem_euclid(360.0),
+                flags,
+                &mut deg,
+                &mut min,
+                &mut sec,
+                &mut fraction,
+                &mut sign,
+            );
+        }
+        // swe_split_deg rounds by adding half a unit, leaving the smaller units populated.
+        if matches!(rounding, Rounding::Minute | Rounding::Degree) {
+            sec = 0;
+        }
+        if rounding == Rounding::Degree {
+            min = 0;
+        }
+        ZodiacPosition {
+            sign: Sign::from_index(sign as usize),
+            degree: deg as u32,
+            minute: min as u32,
+            second: sec as u32,
+            fraction,
+        }
+    }
+
+    /// Degrees within the sign as a decimal, 0-30.
+    pub fn sign_degree(&self) -> f64 {
+        self.degree as f64
+            + self.minute as f64 / 60.0
+            + (self.second as f64 + self.fraction) / 3600.0
+    }
+
+    /// Ecliptic longitude in degrees, 0-360.
+    pub fn longitude(&self) -> f64 {
+        self.sign.start_longitude() + self.sign_degree()
+    }
+}
+
+impl fmt::Display for ZodiacPosition {
+    /// Formats as `23°14'05" cancer`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}°{:02}'{:02}\" {}",
+            self.degree, self.minute, self.second, self.sign
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_attributes_follow_zodiac_order() {
+        assert_eq!(Sign::from_longitude(0.0), Sign::Aries);
+        assert_eq!(Sign::from_longitude(-0.5), Sign::Pisces);
+        assert_eq!(Sign::from_longitude(725.0), Sign::Aries);
+        assert_eq!(Sign::from_longitude(280.0), Sign::Capricorn);
+
+        assert_eq!(Sign::Leo.element(), Element::Fire);
+        assert_eq!(Sign::Virgo.element(), Element::Earth);
+        assert_eq!(Sign::Aquarius.element(), Element::Air);
+        assert_eq!(Sign::Pisces.element(), Element::Water);
+        assert_eq!(Sign::Capricorn.modality(), Modality::Cardinal);
+        assert_eq!(Sign::Scorpio.modality(), Modality::Fixed);
+        assert_eq!(Sign::Gemini.modality(), Modality::Mutable);
+        assert_eq!(Sign::Libra.polarity(), Polarity::Positive);
+        assert_eq!(Sign::Cancer.polarity(), Polarity::Negative);
+
+        assert_eq!(Sign::Scorpio.ruler(), Body::Pluto);
+        assert_eq!(Sign::Scorpio.traditional_ruler(), Body::Mars);
+        assert_eq!(Sign::Cancer.ruler(), Body::Moon);
+
+        assert_eq!(Sign::Pisces.next(), Sign::Aries);
+        assert_eq!(Sign::Aries.previous(), Sign::Pisces);
+        assert_eq!(Sign::Taurus.opposite(), Sign::Scorpio);
+        assert_eq!(Sign::Capricorn.index(), 9);
+
+        for sign in Sign::ALL {
+            assert_eq!(sign.to_string().parse::<Sign>().unwrap(), sign);
+            assert_eq!(Sign::from_longitude(sign.start_longitude() + 15.0), sign);
+        }
+        assert_eq!(" Sagittarius ".parse::<Sign>().unwrap(), Sign::Sagittarius);
+        assert!(matches!(
+            "ophiuchus".parse::<Sign>(),
+            Err(AstroError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn splits_longitude_within_sign() {
+        // 113°14'05.4" is 23°14'05.4" Cancer.
+        let lon = 113.0 + 14.0 / 60.0 + 5.4 / 3600.0;
+        let pos = ZodiacPosition::from_longitude(lon);
+        assert_eq!(
+            (pos.sign, pos.degree, pos.minute, pos.second),
+            (Sign::Cancer, 23, 14, 5)
+        );
+        assert!((pos.fraction - 0.4).abs() < 1e-6);
+        assert!((pos.longitude() - lon).abs() < 1e-9);
+        assert_eq!(pos.to_string(), "23°14'05\" cancer");
+
+        let minute = ZodiacPosition::rounded(lon, Rounding::Minute, Carry::KeepSign);
+        assert_eq!((minute.degree, minute.minute, minute.second), (23, 14, 0));
+        assert_eq!(minute.fraction, 0.0);
+
+        // Just below Leo: rounding stays in Cancer unless carrying into the next sign is allowed.
+        let edge = 119.0 + 59.0 / 60.0 + 59.7 / 3600.0;
+        let kept = ZodiacPosition::rounded(edge, Rounding::Second, Carry::KeepSign);
+        assert_eq!(
+            (kept.sign, kept.degree, kept.minute, kept.second),
+            (Sign::Cancer, 29, 59, 59)
+        );
+        let carried = ZodiacPosition::rounded(edge, Rounding::Second, Carry::NextSign);
+        assert_eq!(
+            (carried.sign, carried.degree, carried.minute, carried.second),
+            (Sign::Leo, 0, 0, 0)
+        );
+        let whole = ZodiacPosition::rounded(23.6, Rounding::Degree, Carry::KeepDegree);
+        assert_eq!((whole.sign, whole.degree), (Sign::Aries, 23));
+        let wrapped = ZodiacPosition::rounded(359.9999, Rounding::Minute, Carry::NextSign);
+        assert_eq!((wrapped.sign, wrapped.degree), (Sign::Aries, 0));
+    }
+}
@@ -0,0 +1,256 @@
+use jiff::civil::DateTime;
+use jiff::tz::{AmbiguousOffset, Offset, TimeZone};
+use libc::{c_double, c_int};
+
+use crate::{ffi, AstroError, BirthData, Calendar};
+
+/// Birth data in local civil time at a named IANA time zone, e.g. `"Europe/Kyiv"`.
+///
+/// Offsets, including historical DST rules and local mean time, come from the tz
+/// database embedded in the crate, so no system files or network access are needed.
+#[derive(Debug, Clone)]
+pub struct LocalBirthData {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    pub hour: i32,               // 0-23, local wall-clock time
+    pub minute: i32,             // 0-59
+    pub second: f64,             // 0.0-59.999
+    pub time_zone: String,       // IANA zone name, e.g. "America/New_York"
+    pub lat: f64,                // latitude in degrees (+N, -S)
+    pub lon: f64,                // longitude in degrees (+E, -W)
+    pub altitude_m: Option<f64>, // elevation above sea level in meters; None means 0
+}
+
+/// Which UTC offset to use for a local time that occurs twice when clocks are set back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Disambiguation {
+    /// Return `AstroError::AmbiguousLocalTime`.
+    #[default]
+    Reject,
+    /// The first occurrence, still on the offset in force before the change (usually DST).
+    Earlier,
+    /// The second occurrence, on the offset in force after the change.
+    Later,
+}
+
+impl LocalBirthData {
+    /// Convert to UTC, rejecting ambiguous and nonexistent local times.
+    pub fn to_utc(&self) -> Result<BirthData, AstroError> {
+        self.to_utc_with(Disambiguation::Reject)
+    }
+
+    /// Convert to UTC, resolving repeated local times with `disambiguation`.
+    ///
+    /// Local times skipped by a forward clock change are always an error.
+    pub fn to_utc_with(&self, disambiguation: Disambiguation) -> Result<BirthData, AstroError> {
+        let offset = self.utc_offset(disambiguation)?;
+        let (mut year, mut month, mut day, mut hour, mut minute) = (0, 0, 0, 0, 0);
+        let mut second: c_double = 0.0;
+        unsafe {
+            ffi::swe_utc_time_zone(
+                self.year as c_int,
+                self.month as c_int,
+                self.day as c_int,
+                self.hour as c_int,
+                self.minute as c_int,
+                self.second,
+                offset,
+                &mut year,
+                &mut month,
+                &mut day,
+                &mut hour,
+                &mut minute,
+                &mut second,
+            );
+        }
+        Ok(BirthData {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            lat: self.lat,
+            lon: self.lon,
+            altitude_m: self.altitude_m,
+            calendar: Calendar::Gregorian,
+        })
+    }
+
+    /// UTC offset in hours east of Greenwich in force at this local time.
+    pub fn utc_offset(&self, disambiguation: Disambiguation) -> Result<f64, AstroError> {
+        let zone = TimeZone::get(&self.time_zone)
+            .map_err(|_| AstroError::UnknownTimeZone(self.time_zone.clone()))?;
+        let local = self.civil_datetime()?;
+        match zone.to_ambiguous_zoned(local).offset() {
+            AmbiguousOffset::Unambiguous { offset } => Ok(hours(offset)),
+            AmbiguousOffset::Fold { before, after } => match disambiguation {
+                Disambiguation::Earlier => Ok(hours(before)),
+                Disambiguation::Later => Ok(hours(after)),
+                Disambiguation::Reject => Err(AstroError::AmbiguousLocalTime {
+                    time: local.to_string(),
+                    zone: self.time_zone.clone(),
+                    earlier_offset: hours(before),
+                    later_offset: hours(after),
+                }),
+            },
+            AmbiguousOffset::Gap { before, after } => Err(AstroError::NonexistentLocalTime {
+                time: local.to_string(),
+                zone: self.time_zone.clone(),
+                offset_before: hours(before),
+                offset_after: hours(after),
+            }),
+        }
+    }
+
+    /// Out-of-range fields fail with `InvalidDate`, as in `BirthData::validate`.
+    fn civil_datetime(&self) -> Result<DateTime, AstroError> {
+        let invalid = || {
+            AstroError::InvalidDate(format!(
+                "invalid local time {}-{:02}-{:02} {:02}:{:02}:{}",
+                self.year, self.month, self.day, self.hour, self.minute, self.second
+            ))
+        };
+        if !(0.0..61.0).contains(&self.second) {
+            return Err(invalid());
+        }
+        // A leap second (60.x) shares the offset of the second before it.
+        let second = self.second.min(59.0) as i8;
+        DateTime::new(
+            i16::try_from(self.year).map_err(|_| invalid())?,
+            i8::try_from(self.month).map_err(|_| invalid())?,
+            i8::try_from(self.day).map_err(|_| invalid())?,
+            i8::try_from(self.hour).map_err(|_| invalid())?,
+            i8::try_from(self.minute).map_err(|_| invalid())?,
+            second,
+            0,
+        )
+        .map_err(|_| invalid())
+    }
+}
+
+fn hours(offset: Offset) -> f64 {
+    offset.seconds() as f64 / 3600.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(zone: &str, hour: i32, minute: i32, date: (i32, i32, i32)) -> LocalBirthData {
+        LocalBirthData {
+            year: date.0,
+            month: date.1,
+            day: date.2,
+            hour,
+            minute,
+            second: 0.0,
+            time_zone: zone.to_string(),
+            lat: 40.7128,
+            lon: -74.0060,
+            altitude_m: None,
+        }
+    }
+
+    fn hms(birth: &BirthData) -> (i32, i32, i32, f64) {
+        (birth.day, birth.hour, birth.minute, birth.second)
+    }
+
+    #[test]
+    fn converts_local_time_with_historical_offsets() {
+        // Summer time in New York is UTC-4.
+        let summer = local("America/New_York", 10, 30, (1990, 7, 15))
+            .to_utc()
+            .unwrap();
+        assert_eq!(hms(&summer), (15, 14, 30, 0.0));
+
+        // Dublin kept Dublin Mean Time, UTC-00:25:21, until 1916.
+        let dublin = local("Europe/Dublin", 12, 0, (1910, 1, 15))
+            .to_utc()
+            .unwrap();
+        assert_eq!((dublin.hour, dublin.minute), (12, 25));
+        assert!((dublin.second - 21.0).abs() < 1e-6);
+
+        // Crossing midnight carries into the previous UTC day.
+        let tokyo = local("Asia/Tokyo", 3, 0, (2000, 1, 1)).to_utc().unwrap();
+        assert_eq!(
+            (tokyo.year, tokyo.month, hms(&tokyo)),
+            (1999, 12, (31, 18, 0, 0.0))
+        );
+
+        let unknown = local("Mars/Olympus_Mons", 0, 0, (2000, 1, 1)).to_utc();
+        assert!(matches!(unknown, Err(AstroError::UnknownTimeZone(_))));
+    }
+
+    #[test]
+    fn reports_ambiguous_and_nonexistent_local_times() {
+        // 2021-03-14 02:30 was skipped in New York when clocks sprang forward.
+        let skipped =
+            local("America/New_York", 2, 30, (2021, 3, 14)).to_utc_with(Disambiguation::Earlier);
+        assert!(matches!(
+            skipped,
+            Err(AstroError::NonexistentLocalTime { offset_before, offset_after, .. })
+                if offset_before == -5.0 && offset_after == -4.0
+        ));
+
+        // 2021-11-07 01:30 happened twice when clocks fell back.
+        let repeated = local("America/New_York", 1, 30, (2021, 11, 7));
+        assert!(matches!(
+            repeated.to_utc(),
+            Err(AstroError::AmbiguousLocalTime { earlier_offset, later_offset, .. })
+                if earlier_offset == -4.0 && later_offset == -5.0
+        ));
+        let first = repeated.to_utc_with(Disambiguation::Earlier).unwrap();
+        let second = repeated.to_utc_with(Disambiguation::Later).unwrap();
+        assert_eq!(hms(&first), (7, 5, 30, 0.0));
+        assert_eq!(hms(&second), (7, 6, 30, 0.0));
+    }
+
+    #[test]
+    fn rejects_invalid_fields_like_birth_data() {
+        let valid = local("Europe/Kyiv", 12, 0, (2000, 1, 1));
+        let cases = [
+            LocalBirthData {
+                month: 13,
+                ..valid.clone()
+            },
+            LocalBirthData {
+                month: 4,
+                day: 31,
+                ..valid.clone()
+            },
+            LocalBirthData {
+                hour: 24,
+                ..valid.clone()
+            },
+            LocalBirthData {
+                minute: 60,
+                ..valid.clone()
+            },
+            LocalBirthData {
+                second: 61.0,
+                ..valid.clone()
+            },
+        ];
+        for case in cases {
+            let utc = BirthData {
+                year: case.year,
+                month: case.month,
+                day: case.day,
+                hour: case.hour,
+                minute: case.minute,
+                second: case.second,
+                lat: case.lat,
+                lon: case.lon,
+                altitude_m: None,
+                calendar: Calendar::Gregorian,
+            };
+            assert!(matches!(utc.validate(), Err(AstroError::InvalidDate(_))));
+            assert!(
+                matches!(case.to_utc(), Err(AstroError::InvalidDate(_))),
+                "{case:?}"
+            );
+        }
+    }
+}